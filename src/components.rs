@@ -32,6 +32,133 @@ pub struct Viewshed {
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct Monster {}
 
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Pet {}
+
+// Monsters spawned as a pack all point at the same leader (the leader points at itself).
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct GroupMember {
+    pub leader: Entity,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Asleep {}
+
+// Set on a monster while it has spotted the player and is actively hunting them.
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Noticed {}
+
+// A spot a monster wants to check out, usually where it last heard a noise.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Investigating {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub enum HungerState {
+    Normal,
+    Hungry,
+    Starving,
+}
+
+// Counts down to the next, hungrier, state.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct HungerClock {
+    pub state: HungerState,
+    pub duration: i32,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct ProvidesFood {}
+
+// Raw food that is only good for cooking with.
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Ingredient {}
+
+// Some recipes can only be cooked standing next to one of these.
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Stove {}
+
+// Names the cookbook loot table rolled for this monster's drops when it dies.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct LootTable {
+    pub table: String,
+}
+
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Stealth {
+    pub level: i32,
+}
+
+/// The modifier an attribute gives to rolls: +1 for every two points above 10,
+/// -1 for every two below.
+pub fn attr_bonus(value: i32) -> i32 {
+    (value - 10).div_euclid(2)
+}
+
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Attributes {
+    pub strength: i32,
+    pub toughness: i32,
+    pub agility: i32,
+    pub smarts: i32,
+}
+
+impl Attributes {
+    /// Extra hit points granted by toughness.
+    pub fn hp_bonus(&self) -> i32 {
+        attr_bonus(self.toughness) * 5
+    }
+
+    /// How much can be carried before it starts slowing you down.
+    pub fn carry_capacity(&self) -> f32 {
+        (20 + self.strength * 2) as f32
+    }
+
+    /// The most that can be carried at all.
+    pub fn max_load(&self) -> f32 {
+        self.carry_capacity() * 1.5
+    }
+}
+
+impl Default for Attributes {
+    fn default() -> Self {
+        Attributes {
+            strength: 10,
+            toughness: 10,
+            agility: 10,
+            smarts: 10,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+pub struct Skill {
+    pub level: i32,
+    pub practice: i32,
+}
+
+impl Skill {
+    /// Records one use of the skill, returning true if it went up a level.
+    pub fn practise(&mut self) -> bool {
+        self.practice += 1;
+        if self.practice >= 10 * (self.level + 1) {
+            self.practice = 0;
+            self.level += 1;
+            return true;
+        }
+        false
+    }
+}
+
+#[derive(Component, ConvertSaveload, Clone, Default)]
+pub struct Skills {
+    pub melee: Skill,
+    pub defense: Skill,
+    pub cooking: Skill,
+}
+
 #[derive(Component, ConvertSaveload, Clone)]
 pub struct Name {
     pub name: String,
@@ -53,24 +180,111 @@ pub struct WantsToMelee {
     pub target: Entity,
 }
 
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub enum DamageType {
+    Blunt,
+    Slashing,
+    Fire,
+    Cold,
+    Spice,
+    Poison,
+    Hunger,
+}
+
+impl DamageType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DamageType::Blunt => "blunt",
+            DamageType::Slashing => "slashing",
+            DamageType::Fire => "fire",
+            DamageType::Cold => "cold",
+            DamageType::Spice => "spice",
+            DamageType::Poison => "poison",
+            DamageType::Hunger => "hunger",
+        }
+    }
+}
+
+// Takes half damage from these types.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Resistant {
+    pub damage_types: Vec<DamageType>,
+}
+
+// Takes double damage from these types.
 #[derive(Component, ConvertSaveload, Clone)]
+pub struct Vulnerable {
+    pub damage_types: Vec<DamageType>,
+}
+
+#[derive(Clone)]
+pub struct DamageInstance {
+    pub amount: i32,
+    pub damage_type: DamageType,
+    pub source: Entity,
+    pub item: Option<Entity>,
+}
+
+// Every hit taken this turn, along with whoever dealt it and the weapon, item or
+// ability they used. This is drained every turn, so it never needs saving.
+#[derive(Component, Clone)]
 pub struct SufferDamage {
-    pub amount: Vec<i32>,
+    pub instances: Vec<DamageInstance>,
 }
 
 impl SufferDamage {
-    pub fn new_damage(store: &mut WriteStorage<SufferDamage>, victim: Entity, amount: i32) {
+    pub fn new_damage(
+        store: &mut WriteStorage<SufferDamage>,
+        victim: Entity,
+        amount: i32,
+        damage_type: DamageType,
+        source: Entity,
+        item: Option<Entity>,
+    ) {
+        let instance = DamageInstance {
+            amount,
+            damage_type,
+            source,
+            item,
+        };
         if let Some(suffering) = store.get_mut(victim) {
-            suffering.amount.push(amount);
+            suffering.instances.push(instance);
         } else {
             let dmg = SufferDamage {
-                amount: vec![amount],
+                instances: vec![instance],
             };
             store.insert(victim, dmg).expect("Unable to insert damage");
         }
     }
 }
 
+// What landed the killing blow, e.g. "Potato's Fork". Only lives until the
+// body is cleaned up, so it isn't saved.
+#[derive(Component, Clone)]
+pub struct KilledBy {
+    pub cause: String,
+}
+
+// Someone who hurt us or a friend of ours, and should be hit back. Cleared when
+// they die, and not worth saving.
+#[derive(Component, Clone)]
+pub struct Grudge {
+    pub against: Entity,
+}
+
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Experience {
+    pub level: i32,
+    pub xp: i32,
+}
+
+impl Experience {
+    /// Total experience needed to reach the next level.
+    pub fn next_level_xp(&self) -> i32 {
+        25 * self.level * self.level
+    }
+}
+
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct Item {}
 
@@ -99,6 +313,63 @@ pub struct WantsToUseItem {
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct Consumable {}
 
+// How heavy one of an item is, in pounds.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Weight {
+    pub pounds: f32,
+}
+
+// Total weight of everything a creature is carrying, worked out each turn.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Encumbrance {
+    pub carried: f32,
+}
+
+// A pile of identical items sharing one entity and one inventory line.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Stackable {
+    pub quantity: i32,
+}
+
+// What one of an item is worth, before any shopkeeper's cut.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Value {
+    pub gold: i32,
+}
+
+// A pile of coins, which goes straight into the purse of whoever picks it up.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Gold {
+    pub amount: i32,
+}
+
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Purse {
+    pub gold: i32,
+}
+
+// Somebody who buys and sells. Their stock is whatever is in their backpack.
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Vendor {}
+
+// A wand or gadget that works `remaining` more times. Once empty it goes inert,
+// or falls apart if it `crumbles`.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Charges {
+    pub remaining: i32,
+    pub max: i32,
+    pub crumbles: bool,
+}
+
+// A magic item going by its unidentified name; `real_name` is what it really is.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Unidentified {
+    pub real_name: String,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct IdentifiesItem {}
+
 #[derive(Component, ConvertSaveload, Clone)]
 pub struct ProvidesHealing {
     pub heal_amount: i32,
@@ -112,6 +383,7 @@ pub struct Ranged {
 #[derive(Component, ConvertSaveload, Clone)]
 pub struct InflictsDamage {
     pub damage: i32,
+    pub damage_type: DamageType,
 }
 
 #[derive(Component, ConvertSaveload, Clone)]
@@ -119,11 +391,140 @@ pub struct AreaOfEffect {
     pub radius: i32,
 }
 
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub enum StatusEffect {
+    Confused,
+    Poisoned,
+    Burning,
+    Slowed,
+    Hasted,
+    Regenerating,
+    WellFed,
+}
+
+impl StatusEffect {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatusEffect::Confused => "confused",
+            StatusEffect::Poisoned => "poisoned",
+            StatusEffect::Burning => "burning",
+            StatusEffect::Slowed => "slowed",
+            StatusEffect::Hasted => "hasted",
+            StatusEffect::Regenerating => "regenerating",
+            StatusEffect::WellFed => "well fed",
+        }
+    }
+
+    /// Short label and colour for the status bar.
+    pub fn icon(&self) -> (&'static str, RGB) {
+        match self {
+            StatusEffect::Confused => ("Conf", RGB::named(rltk::MAGENTA)),
+            StatusEffect::Poisoned => ("Pois", RGB::named(rltk::GREEN)),
+            StatusEffect::Burning => ("Burn", RGB::named(rltk::ORANGE)),
+            StatusEffect::Slowed => ("Slow", RGB::named(rltk::LIGHT_BLUE)),
+            StatusEffect::Hasted => ("Fast", RGB::named(rltk::CYAN)),
+            StatusEffect::Regenerating => ("Regen", RGB::named(rltk::PINK)),
+            StatusEffect::WellFed => ("Fed", RGB::named(rltk::GOLD)),
+        }
+    }
+
+    /// Effects that cancel each other out when both are applied.
+    fn opposite(&self) -> Option<StatusEffect> {
+        match self {
+            StatusEffect::Slowed => Some(StatusEffect::Hasted),
+            StatusEffect::Hasted => Some(StatusEffect::Slowed),
+            _ => None,
+        }
+    }
+}
+
+// Whoever put the effect on, and with what, so a poisoning or burning that kills
+// is credited to them. Entities can't be saved from in here, so after a reload
+// the victim is left to blame.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ActiveStatus {
+    pub effect: StatusEffect,
+    pub turns: i32,
+    #[serde(skip)]
+    pub source: Option<Entity>,
+    #[serde(skip)]
+    pub item: Option<Entity>,
+}
+
+// Everything currently affecting an entity, ticked down once per turn.
+#[derive(Component, ConvertSaveload, Clone, Default)]
+pub struct StatusEffects {
+    pub active: Vec<ActiveStatus>,
+}
+
+impl StatusEffects {
+    pub fn has(&self, effect: StatusEffect) -> bool {
+        self.active.iter().any(|s| s.effect == effect)
+    }
+
+    pub fn turns_left(&self, effect: StatusEffect) -> i32 {
+        self.active
+            .iter()
+            .find(|s| s.effect == effect)
+            .map_or(0, |s| s.turns)
+    }
+
+    /// Applies an effect. Poison stacks its duration, everything else just refreshes
+    /// to the longer of the two, and opposites cancel out instead of applying. The
+    /// latest `source` and `item` take the credit for any damage it does.
+    pub fn add(
+        &mut self,
+        effect: StatusEffect,
+        turns: i32,
+        source: Option<Entity>,
+        item: Option<Entity>,
+    ) {
+        if let Some(opposite) = effect.opposite() {
+            if self.has(opposite) {
+                self.active.retain(|s| s.effect != opposite);
+                return;
+            }
+        }
+        if let Some(existing) = self.active.iter_mut().find(|s| s.effect == effect) {
+            if effect == StatusEffect::Poisoned {
+                existing.turns += turns;
+            } else {
+                existing.turns = i32::max(existing.turns, turns);
+            }
+            existing.source = source;
+            existing.item = item;
+        } else {
+            self.active.push(ActiveStatus {
+                effect,
+                turns,
+                source,
+                item,
+            });
+        }
+    }
+
+    /// Slowed creatures only get to act every other turn.
+    pub fn loses_turn(&self) -> bool {
+        self.has(StatusEffect::Slowed) && self.turns_left(StatusEffect::Slowed) % 2 == 0
+    }
+}
+
+// Puts a status effect on whoever the item or ability is used on.
 #[derive(Component, ConvertSaveload, Clone)]
-pub struct Confusion {
+pub struct InflictsStatus {
+    pub effect: StatusEffect,
     pub turns: i32,
 }
 
+// Lets a monster use an ability entity (carrying InflictsDamage, AreaOfEffect
+// and/or InflictsStatus) on the player from a distance.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct RangedAttacker {
+    pub ability: Entity,
+    pub min_range: i32,
+    pub max_range: i32,
+}
+
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct SerializeMe {}
 
@@ -131,14 +532,66 @@ pub struct SerializeMe {}
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct SerializationHelper {
     pub map: super::map::Map,
+    pub turn: i32,
+    pub identification: super::identification::Identification,
 }
 
 #[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
 pub enum EquipmentSlot {
     Melee,
     Shield,
+    Ranged,
+    Head,
+    Body,
+    Hands,
+    Feet,
+    Ring,
 }
 
+/// Every slot, in the order the equipment screen lists them.
+pub const EQUIPMENT_SLOTS: [EquipmentSlot; 8] = [
+    EquipmentSlot::Melee,
+    EquipmentSlot::Shield,
+    EquipmentSlot::Ranged,
+    EquipmentSlot::Head,
+    EquipmentSlot::Body,
+    EquipmentSlot::Hands,
+    EquipmentSlot::Feet,
+    EquipmentSlot::Ring,
+];
+
+impl EquipmentSlot {
+    pub fn name(&self) -> &'static str {
+        match self {
+            EquipmentSlot::Melee => "Melee",
+            EquipmentSlot::Shield => "Shield",
+            EquipmentSlot::Ranged => "Ranged",
+            EquipmentSlot::Head => "Head",
+            EquipmentSlot::Body => "Body",
+            EquipmentSlot::Hands => "Hands",
+            EquipmentSlot::Feet => "Feet",
+            EquipmentSlot::Ring => "Ring",
+        }
+    }
+}
+
+// Gear that can't be taken off once worn. `known` once the player has found out.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Cursed {
+    pub known: bool,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct RemovesCurse {}
+
+// Gear an identify scroll has shown not to be cursed.
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct KnownClean {}
+
+// A melee weapon that needs both paws, so it takes up the shield slot too.
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct TwoHanded {}
+
 #[derive(Component, ConvertSaveload, Clone)]
 pub struct Equippable {
     pub slot: EquipmentSlot,
@@ -155,6 +608,82 @@ pub struct MeleePowerBonus {
     pub power: i32,
 }
 
+// Damage dealt by a wielded weapon, e.g. 1d6+1.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct MeleeWeapon {
+    pub damage: rltk::DiceType,
+    pub damage_type: DamageType,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub enum Affix {
+    Fire,
+    Speed,
+    Vampiric,
+}
+
+impl Affix {
+    /// What a weapon with this affix is called, e.g. "Fork of Fire".
+    pub fn weapon_name(&self, base: &str) -> String {
+        match self {
+            Affix::Fire => format!("{} of Fire", base),
+            Affix::Speed => format!("{} of Speed", base),
+            Affix::Vampiric => format!("Vampiric {}", base),
+        }
+    }
+}
+
+// A special property rolled onto a melee weapon, which kicks in when it lands a hit.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct WeaponAffix {
+    pub affix: Affix,
+}
+
+// Fires a projectile using up one piece of the named ammunition per shot.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct RangedWeapon {
+    pub range: i32,
+    pub damage: rltk::DiceType,
+    pub damage_type: DamageType,
+    pub ammo: String,
+}
+
+// A pile of shots for any ranged weapon that takes this kind of ammo.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Ammunition {
+    pub kind: String,
+}
+
+// Damage done when thrown at someone, for things made to be thrown (or just
+// sharp). Anything else thumps them for a little blunt damage by its weight.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct ThrowDamage {
+    pub damage: rltk::DiceType,
+    pub damage_type: DamageType,
+}
+
+// Breaks when thrown, splashing its effects over the area it lands in.
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Fragile {}
+
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct WantsToThrowItem {
+    pub item: Entity,
+    pub target: rltk::Point,
+}
+
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct WantsToShoot {
+    pub target: rltk::Point,
+}
+
+// Damage dealt by a creature's own teeth, claws or stalks when it has no weapon.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct NaturalAttack {
+    pub damage: rltk::DiceType,
+    pub damage_type: DamageType,
+}
+
 #[derive(Component, ConvertSaveload, Clone)]
 pub struct DefenseBonus {
     pub defense: i32,
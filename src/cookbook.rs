@@ -0,0 +1,233 @@
+use super::{
+    gamelog::GameLog,
+    inventory_system::{adjust_load, can_carry},
+    random_table::RandomTable,
+    spawner, DamageType, InBackpack, Ingredient, Name, Position, Skills, Stackable, StatusEffect,
+    Stove, Weight,
+};
+use rltk::Point;
+use serde::Deserialize;
+use specs::prelude::*;
+
+const RECIPE_DATA: &str = include_str!("../resources/recipes.json");
+
+// Everything that can be harvested and cooked, loaded from resources/recipes.json.
+#[derive(Deserialize)]
+pub struct Cookbook {
+    pub ingredients: Vec<IngredientData>,
+    pub loot_tables: Vec<LootTableData>,
+    pub recipes: Vec<Recipe>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct IngredientData {
+    pub name: String,
+    pub glyph: u16,
+    pub colour: (u8, u8, u8),
+    pub weight: f32,
+}
+
+#[derive(Deserialize)]
+pub struct LootTableData {
+    pub name: String,
+    pub drops: Vec<LootDrop>,
+}
+
+#[derive(Deserialize)]
+pub struct LootDrop {
+    pub item: String,
+    pub weight: i32,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub struct RecipeStatus {
+    pub effect: StatusEffect,
+    pub turns: i32,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub struct RecipeDamage {
+    pub amount: i32,
+    pub damage_type: DamageType,
+}
+
+// A dish, the ingredients it uses up, and the item effects it ends up with.
+#[derive(Deserialize, Clone)]
+pub struct Recipe {
+    pub name: String,
+    pub ingredients: Vec<String>,
+    #[serde(default)]
+    pub needs_stove: bool,
+    pub glyph: u16,
+    pub colour: (u8, u8, u8),
+    pub weight: f32,
+    #[serde(default)]
+    pub food: bool,
+    #[serde(default)]
+    pub fragile: bool,
+    pub heal: Option<i32>,
+    pub status: Option<RecipeStatus>,
+    pub damage: Option<RecipeDamage>,
+    pub range: Option<i32>,
+    pub radius: Option<i32>,
+}
+
+impl Cookbook {
+    pub fn load() -> Cookbook {
+        serde_json::from_str(RECIPE_DATA).expect("Unable to parse recipes")
+    }
+
+    pub fn ingredient(&self, name: &str) -> Option<&IngredientData> {
+        self.ingredients.iter().find(|i| i.name == name)
+    }
+
+    /// Builds the drop table for a monster; unknown tables never drop anything.
+    pub fn loot_table(&self, name: &str) -> RandomTable {
+        let mut table = RandomTable::new();
+        if let Some(loot) = self.loot_tables.iter().find(|l| l.name == name) {
+            for drop in loot.drops.iter() {
+                table = table.add(&drop.item, drop.weight);
+            }
+        }
+        table
+    }
+}
+
+pub fn player_near_stove(ecs: &World) -> bool {
+    let player_pos = ecs.fetch::<Point>();
+    let positions = ecs.read_storage::<Position>();
+    let stoves = ecs.read_storage::<Stove>();
+    (&positions, &stoves).join().any(|(pos, _stove)| {
+        rltk::DistanceAlg::Pythagoras.distance2d(Point::new(pos.x, pos.y), *player_pos) < 1.5
+    })
+}
+
+/// Works out how many to take from each of the player's ingredient stacks to
+/// make `recipe`, or None if the player doesn't have enough of something.
+fn gather_ingredients(ecs: &World, recipe: &Recipe) -> Option<Vec<(Entity, i32)>> {
+    let player_entity = ecs.fetch::<Entity>();
+    let entities = ecs.entities();
+    let names = ecs.read_storage::<Name>();
+    let backpack = ecs.read_storage::<InBackpack>();
+    let ingredients = ecs.read_storage::<Ingredient>();
+    let stacks = ecs.read_storage::<Stackable>();
+
+    let mut gathered: Vec<(Entity, i32)> = Vec::new();
+    for wanted in recipe.ingredients.iter() {
+        let found = (&entities, &names, &backpack, &ingredients)
+            .join()
+            .find(|(e, name, pack, _i)| {
+                let quantity = stacks.get(*e).map_or(1, |s| s.quantity);
+                let taken = gathered
+                    .iter()
+                    .find(|(g, _n)| g == e)
+                    .map_or(0, |(_g, n)| *n);
+                pack.owner == *player_entity && name.name == *wanted && taken < quantity
+            })
+            .map(|(e, _name, _pack, _i)| e)?;
+        match gathered.iter_mut().find(|(g, _n)| *g == found) {
+            Some((_g, n)) => *n += 1,
+            None => gathered.push((found, 1)),
+        }
+    }
+    Some(gathered)
+}
+
+pub fn can_cook(ecs: &World, recipe: &Recipe) -> bool {
+    (!recipe.needs_stove || player_near_stove(ecs)) && gather_ingredients(ecs, recipe).is_some()
+}
+
+/// Turns the player's ingredients into the recipe's dish. Returns true if it took
+/// the player's turn.
+pub fn cook(ecs: &mut World, recipe_index: usize) -> bool {
+    let recipe = ecs.fetch::<Cookbook>().recipes[recipe_index].clone();
+
+    if recipe.needs_stove && !player_near_stove(ecs) {
+        ecs.write_resource::<GameLog>()
+            .entries
+            .push(format!("You need a stove to make {}.", recipe.name));
+        return false;
+    }
+    let gathered = match gather_ingredients(ecs, &recipe) {
+        Some(gathered) => gathered,
+        None => {
+            ecs.write_resource::<GameLog>().entries.push(format!(
+                "You don't have the ingredients for {}.",
+                recipe.name
+            ));
+            return false;
+        }
+    };
+
+    // The dish can't take us past what we can lift, once the ingredients are gone
+    let player_entity = *ecs.fetch::<Entity>();
+    let ingredient_weight: f32 = {
+        let weights = ecs.read_storage::<Weight>();
+        gathered
+            .iter()
+            .map(|(ingredient, used)| {
+                weights.get(*ingredient).map_or(0.0, |w| w.pounds) * *used as f32
+            })
+            .sum()
+    };
+    let added_weight = recipe.weight - ingredient_weight;
+    if !can_carry(ecs, player_entity, added_weight) {
+        ecs.write_resource::<GameLog>().entries.push(format!(
+            "The {} would be too heavy for you to carry.",
+            recipe.name
+        ));
+        return false;
+    }
+    adjust_load(ecs, player_entity, added_weight);
+
+    for (ingredient, used) in gathered {
+        let left = {
+            let mut stacks = ecs.write_storage::<Stackable>();
+            match stacks.get_mut(ingredient) {
+                Some(stack) => {
+                    stack.quantity -= used;
+                    stack.quantity
+                }
+                None => 0,
+            }
+        };
+        if left < 1 {
+            ecs.delete_entity(ingredient)
+                .expect("Unable to delete ingredient");
+        }
+    }
+
+    // The dish joins any of the same we're already carrying
+    let merged = {
+        let names = ecs.read_storage::<Name>();
+        let backpack = ecs.read_storage::<InBackpack>();
+        let mut stacks = ecs.write_storage::<Stackable>();
+        match (&names, &backpack, &mut stacks)
+            .join()
+            .find(|(name, pack, _s)| pack.owner == player_entity && name.name == recipe.name)
+        {
+            Some((_name, _pack, stack)) => {
+                stack.quantity += 1;
+                true
+            }
+            None => false,
+        }
+    };
+    if !merged {
+        spawner::dish(ecs, &recipe, player_entity);
+    }
+
+    let mut log = ecs.write_resource::<GameLog>();
+    log.entries
+        .push(format!("You cook up some {}.", recipe.name));
+    let mut skills = ecs.write_storage::<Skills>();
+    if let Some(skills) = skills.get_mut(player_entity) {
+        if skills.cooking.practise() {
+            log.entries.push(format!(
+                "Your cooking skill improves to {}.",
+                skills.cooking.level
+            ));
+        }
+    }
+    true
+}
@@ -1,23 +1,119 @@
-use super::{CombatStats, GameLog, Map, Name, Player, Position, SufferDamage};
+use super::{
+    cookbook::Cookbook, spawner, Asleep, CombatStats, DamageInstance, Equipped, Experience,
+    GameLog, GroupMember, Grudge, InBackpack, KilledBy, LootTable, Map, Monster, Name, Pet, Player,
+    Position, RangedAttacker, Resistant, SufferDamage, Vulnerable,
+};
 use crate::RunState;
-use rltk::console;
+use rltk::{console, RandomNumberGenerator};
 use specs::prelude::*;
 
 pub struct DamageSystem {}
 
 impl<'a> System<'a> for DamageSystem {
+    #[allow(clippy::type_complexity)]
     type SystemData = (
         WriteStorage<'a, CombatStats>,
         WriteStorage<'a, SufferDamage>,
         ReadStorage<'a, Position>,
         WriteExpect<'a, Map>,
         Entities<'a>,
+        WriteStorage<'a, Asleep>,
+        WriteStorage<'a, Experience>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Name>,
+        WriteExpect<'a, GameLog>,
+        ReadStorage<'a, Resistant>,
+        ReadStorage<'a, Vulnerable>,
+        ReadStorage<'a, Monster>,
+        ReadStorage<'a, Pet>,
+        WriteStorage<'a, Grudge>,
+        WriteStorage<'a, KilledBy>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (mut stats, mut damage, positions, mut map, entities) = data;
-        for (entity, mut stats, damage) in (&entities, &mut stats, &damage).join() {
-            stats.hp -= damage.amount.iter().sum::<i32>();
+        let (
+            mut stats,
+            mut damage,
+            positions,
+            mut map,
+            entities,
+            mut asleep,
+            mut experience,
+            players,
+            names,
+            mut log,
+            resistances,
+            vulnerabilities,
+            monsters,
+            pets,
+            mut grudges,
+            mut killed_by,
+        ) = data;
+
+        let mut kills: Vec<(Entity, i32)> = Vec::new();
+        let mut grudges_to_add: Vec<(Entity, Entity)> = Vec::new();
+        let mut causes_of_death: Vec<(Entity, String)> = Vec::new();
+        for (entity, stats, damage) in (&entities, &mut stats, &damage).join() {
+            let visible = positions
+                .get(entity)
+                .is_some_and(|pos| map.visible_tiles[map.xy_idx(pos.x, pos.y)]);
+            for hit in damage.instances.iter() {
+                let mut amount = hit.amount;
+                let resists = resistances
+                    .get(entity)
+                    .is_some_and(|r| r.damage_types.contains(&hit.damage_type));
+                let vulnerable = vulnerabilities
+                    .get(entity)
+                    .is_some_and(|v| v.damage_types.contains(&hit.damage_type));
+                if resists && !vulnerable {
+                    amount = i32::max(1, amount / 2);
+                    if visible {
+                        if let Some(name) = names.get(entity) {
+                            log.entries.push(format!(
+                                "{} resists the {} damage, taking only {} hp.",
+                                name.name,
+                                hit.damage_type.name(),
+                                amount
+                            ));
+                        }
+                    }
+                } else if vulnerable && !resists {
+                    amount *= 2;
+                    if visible {
+                        if let Some(name) = names.get(entity) {
+                            log.entries.push(format!(
+                                "{} is vulnerable to {} damage, taking {} hp!",
+                                name.name,
+                                hit.damage_type.name(),
+                                amount
+                            ));
+                        }
+                    }
+                }
+
+                let was_alive = stats.hp > 0;
+                stats.hp -= amount;
+                // Whoever lands the killing blow earns experience based on how tough the victim was
+                if was_alive && stats.hp < 1 {
+                    kills.push((hit.source, stats.max_hp));
+                    causes_of_death.push((entity, describe_hit(hit, entity, &names)));
+                }
+
+                // Allies stick up for each other: pets go after anything that hurts the
+                // player or a pet, and monsters turn on a pet that bites them.
+                if hit.source != entity && entities.is_alive(hit.source) {
+                    let victim_is_friendly =
+                        players.get(entity).is_some() || pets.get(entity).is_some();
+                    if victim_is_friendly && monsters.get(hit.source).is_some() {
+                        for (pet, _pet) in (&entities, &pets).join() {
+                            grudges_to_add.push((pet, hit.source));
+                        }
+                    } else if monsters.get(entity).is_some() && pets.get(hit.source).is_some() {
+                        grudges_to_add.push((entity, hit.source));
+                    }
+                }
+            }
+            asleep.remove(entity);
             let pos = positions.get(entity);
             if let Some(pos) = pos {
                 let idx = map.xy_idx(pos.x, pos.y);
@@ -25,6 +121,53 @@ impl<'a> System<'a> for DamageSystem {
             }
         }
         damage.clear();
+
+        for (avenger, against) in grudges_to_add {
+            grudges
+                .insert(avenger, Grudge { against })
+                .expect("Unable to insert grudge");
+        }
+        for (victim, cause) in causes_of_death {
+            killed_by
+                .insert(victim, KilledBy { cause })
+                .expect("Unable to insert cause of death");
+        }
+
+        for (killer, xp) in kills {
+            if let Some(exp) = experience.get_mut(killer) {
+                exp.xp += xp;
+                // The player picks their own improvements on the level up screen, anyone
+                // else just gets tougher.
+                if players.get(killer).is_none() {
+                    while exp.xp >= exp.next_level_xp() {
+                        exp.level += 1;
+                        if let Some(killer_stats) = stats.get_mut(killer) {
+                            killer_stats.max_hp += 5;
+                            killer_stats.hp += 5;
+                            killer_stats.power += 1;
+                        }
+                        if let Some(name) = names.get(killer) {
+                            log.entries
+                                .push(format!("{} is now level {}!", name.name, exp.level));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Describes who (and what) dealt a hit, such as "Potato's Fork", "Tomato" or "poison".
+fn describe_hit(hit: &DamageInstance, victim: Entity, names: &ReadStorage<Name>) -> String {
+    let attacker = names
+        .get(hit.source)
+        .map_or("something".to_string(), |n| n.name.clone());
+    let item = hit.item.and_then(|item| names.get(item)).map(|n| &n.name);
+    match item {
+        None if hit.source == victim => hit.damage_type.name().to_string(),
+        None => attacker,
+        Some(item) if hit.source == victim => format!("their own {}", item),
+        Some(item) => format!("{}'s {}", attacker, item),
     }
 }
 
@@ -34,6 +177,8 @@ pub fn delete_the_dead(ecs: &mut World) {
         let combat_stats = ecs.read_storage::<CombatStats>();
         let players = ecs.read_storage::<Player>();
         let names = ecs.read_storage::<Name>();
+        let ranged_attackers = ecs.read_storage::<RangedAttacker>();
+        let killed_by = ecs.read_storage::<KilledBy>();
         let entities = ecs.entities();
         let mut log = ecs.write_resource::<GameLog>();
         for (entity, stats) in (&entities, &combat_stats).join() {
@@ -43,9 +188,19 @@ pub fn delete_the_dead(ecs: &mut World) {
                     None => {
                         let victim_name = names.get(entity);
                         if let Some(victim_name) = victim_name {
-                            log.entries.push(format!("{} is dead", &victim_name.name))
+                            match killed_by.get(entity) {
+                                None => log.entries.push(format!("{} is dead", &victim_name.name)),
+                                Some(killed_by) => log.entries.push(format!(
+                                    "{} is killed by {}.",
+                                    &victim_name.name, killed_by.cause
+                                )),
+                            }
+                        }
+                        dead.push(entity);
+                        // Abilities only exist for the monster that uses them
+                        if let Some(ranged) = ranged_attackers.get(entity) {
+                            dead.push(ranged.ability);
                         }
-                        dead.push(entity)
                     }
                     Some(_) => {
                         let mut runstate = ecs.write_resource::<RunState>();
@@ -56,7 +211,91 @@ pub fn delete_the_dead(ecs: &mut World) {
         }
     }
 
+    // Nobody holds a grudge against the dead
+    {
+        let entities = ecs.entities();
+        let mut grudges = ecs.write_storage::<Grudge>();
+        let settled: Vec<Entity> = (&entities, &grudges)
+            .join()
+            .filter(|(_avenger, grudge)| dead.contains(&grudge.against))
+            .map(|(avenger, _grudge)| avenger)
+            .collect();
+        for avenger in settled {
+            grudges.remove(avenger);
+        }
+    }
+
+    // A pack that loses its leader falls in behind whoever is left
+    {
+        let entities = ecs.entities();
+        let mut groups = ecs.write_storage::<GroupMember>();
+        for victim in dead.iter() {
+            let survivors: Vec<Entity> = (&entities, &groups)
+                .join()
+                .filter(|(member, group)| group.leader == *victim && !dead.contains(member))
+                .map(|(member, _group)| member)
+                .collect();
+            if let Some(new_leader) = survivors.first() {
+                for survivor in survivors.iter() {
+                    if let Some(group) = groups.get_mut(*survivor) {
+                        group.leader = *new_leader;
+                    }
+                }
+            }
+        }
+    }
+
+    // Anything the dead were carrying falls to the floor where they died
+    {
+        let entities = ecs.entities();
+        let mut positions = ecs.write_storage::<Position>();
+        let mut backpack = ecs.write_storage::<InBackpack>();
+        let mut equipped = ecs.write_storage::<Equipped>();
+        let mut dropped: Vec<(Entity, Position)> = Vec::new();
+        for victim in dead.iter() {
+            if let Some(pos) = positions.get(*victim) {
+                for (item, pack) in (&entities, &backpack).join() {
+                    if pack.owner == *victim {
+                        dropped.push((item, pos.clone()));
+                    }
+                }
+                for (item, eq) in (&entities, &equipped).join() {
+                    if eq.owner == *victim {
+                        dropped.push((item, pos.clone()));
+                    }
+                }
+            }
+        }
+        for (item, pos) in dropped {
+            backpack.remove(item);
+            equipped.remove(item);
+            positions
+                .insert(item, pos)
+                .expect("Unable to insert position");
+        }
+    }
+
+    // Slain vegetables leave something behind for the pot
+    let mut drops: Vec<(String, Position)> = Vec::new();
+    {
+        let positions = ecs.read_storage::<Position>();
+        let loot_tables = ecs.read_storage::<LootTable>();
+        let cookbook = ecs.fetch::<Cookbook>();
+        let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+        for victim in dead.iter() {
+            if let (Some(pos), Some(loot)) = (positions.get(*victim), loot_tables.get(*victim)) {
+                let drop = cookbook.loot_table(&loot.table).roll(&mut rng);
+                if drop != "None" {
+                    drops.push((drop, pos.clone()));
+                }
+            }
+        }
+    }
+
     for victim in dead {
         ecs.delete_entity(victim).expect("Unable to delete");
     }
+    for (drop, pos) in drops {
+        spawner::ingredient(ecs, pos.x, pos.y, &drop);
+    }
 }
@@ -0,0 +1,99 @@
+use super::{
+    Asleep, GameLog, Investigating, Map, Monster, Name, Noticed, Position, RunState, Stealth,
+    Viewshed,
+};
+use rltk::{Point, RandomNumberGenerator};
+use specs::prelude::*;
+
+pub struct DetectionSystem {}
+
+impl<'a> System<'a> for DetectionSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        ReadExpect<'a, Map>,
+        ReadExpect<'a, Point>,
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, RunState>,
+        WriteExpect<'a, RandomNumberGenerator>,
+        WriteExpect<'a, GameLog>,
+        Entities<'a>,
+        ReadStorage<'a, Monster>,
+        ReadStorage<'a, Viewshed>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, Asleep>,
+        ReadStorage<'a, Stealth>,
+        WriteStorage<'a, Noticed>,
+        WriteStorage<'a, Investigating>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            map,
+            player_pos,
+            player_entity,
+            runstate,
+            mut rng,
+            mut log,
+            entities,
+            monsters,
+            viewsheds,
+            positions,
+            names,
+            asleep,
+            stealth,
+            mut noticed,
+            mut investigating,
+        ) = data;
+
+        if *runstate != RunState::MonsterTurn {
+            return;
+        }
+
+        let stealth_level = stealth.get(*player_entity).map_or(0, |s| s.level);
+
+        for (entity, _monster, viewshed, pos) in
+            (&entities, &monsters, &viewsheds, &positions).join()
+        {
+            if asleep.get(entity).is_some() {
+                continue;
+            }
+
+            if !viewshed.visible_tiles.contains(&*player_pos) {
+                // Lost sight of them, so head for where they were last seen
+                if noticed.remove(entity).is_some() {
+                    investigating
+                        .insert(
+                            entity,
+                            Investigating {
+                                x: player_pos.x,
+                                y: player_pos.y,
+                            },
+                        )
+                        .expect("Unable to insert investigation");
+                }
+                continue;
+            }
+
+            if noticed.get(entity).is_some() {
+                continue;
+            }
+
+            // Sneakier players and longer distances make it harder to be spotted
+            let distance =
+                rltk::DistanceAlg::Pythagoras.distance2d(Point::new(pos.x, pos.y), *player_pos);
+            let chance = i32::max(5, 100 - (stealth_level * 10) - (distance as i32 * 5));
+            if rng.roll_dice(1, 100) <= chance {
+                noticed
+                    .insert(entity, Noticed {})
+                    .expect("Unable to insert noticed");
+                investigating.remove(entity);
+                if map.visible_tiles[map.xy_idx(pos.x, pos.y)] {
+                    if let Some(name) = names.get(entity) {
+                        log.entries.push(format!("{} notices you!", name.name));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use super::{Attributes, Encumbrance, Equipped, InBackpack, Stackable, Weight};
+use specs::prelude::*;
+use std::collections::HashMap;
+
+pub struct EncumbranceSystem {}
+
+impl<'a> System<'a> for EncumbranceSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        Entities<'a>,
+        ReadStorage<'a, Attributes>,
+        WriteStorage<'a, Encumbrance>,
+        ReadStorage<'a, Weight>,
+        ReadStorage<'a, Stackable>,
+        ReadStorage<'a, InBackpack>,
+        ReadStorage<'a, Equipped>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, attributes, mut encumbrance, weights, stacks, backpack, equipped) = data;
+
+        // Everything in a backpack or worn counts towards its owner's load
+        let mut carried: HashMap<Entity, f32> = HashMap::new();
+        for (item, weight) in (&entities, &weights).join() {
+            let owner = match (backpack.get(item), equipped.get(item)) {
+                (Some(pack), _) => pack.owner,
+                (None, Some(worn)) => worn.owner,
+                (None, None) => continue,
+            };
+            let quantity = stacks.get(item).map_or(1, |stack| stack.quantity);
+            *carried.entry(owner).or_insert(0.0) += weight.pounds * quantity as f32;
+        }
+
+        for (entity, _attributes) in (&entities, &attributes).join() {
+            let carried = carried.get(&entity).copied().unwrap_or(0.0);
+            encumbrance
+                .insert(entity, Encumbrance { carried })
+                .expect("Unable to insert encumbrance");
+        }
+    }
+}
@@ -0,0 +1,5 @@
+/// Counts full game turns: one player turn plus the monsters' reply.
+#[derive(Default)]
+pub struct GameClock {
+    pub turn: i32,
+}
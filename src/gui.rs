@@ -1,6 +1,9 @@
 use super::{
-    CombatStats, Equipped, GameLog, InBackpack, Map, Name, Player, Position, RunState, State,
-    Viewshed,
+    cookbook::{self, Cookbook},
+    shop, Attributes, Charges, CombatStats, Cursed, Encumbrance, EquipmentSlot, Equippable,
+    Equipped, Experience, GameClock, GameLog, HungerClock, HungerState, InBackpack, KilledBy,
+    KnownClean, Map, Name, Player, Position, Purse, RunState, Stackable, State, StatusEffect,
+    StatusEffects, TwoHanded, Unidentified, Viewshed, EQUIPMENT_SLOTS,
 };
 use rltk::{Point, Rltk, VirtualKeyCode, RGB};
 use specs::prelude::*;
@@ -60,13 +63,91 @@ pub fn draw_ui(ecs: &World, ctx: &mut Rltk) {
         ctx.draw_bar_horizontal(
             28,
             42,
-            51,
+            30,
             stats.hp,
             stats.max_hp,
             RGB::named(rltk::RED),
             RGB::named(rltk::BLACK),
         );
     }
+    let experience = ecs.read_storage::<Experience>();
+    for (_player, exp) in (&players, &experience).join() {
+        let level = format!("Lv {} XP {}/{}", exp.level, exp.xp, exp.next_level_xp());
+        ctx.print_color(
+            60,
+            42,
+            RGB::named(rltk::CYAN),
+            RGB::named(rltk::BLACK),
+            &level,
+        );
+    }
+    let hunger_clocks = ecs.read_storage::<HungerClock>();
+    let statuses = ecs.read_storage::<StatusEffects>();
+    for (player, _player, hunger) in (&ecs.entities(), &players, &hunger_clocks).join() {
+        let well_fed = statuses
+            .get(player)
+            .is_some_and(|s| s.has(StatusEffect::WellFed));
+        let (label, colour) = match hunger.state {
+            HungerState::Normal if well_fed => ("Well Fed", RGB::named(rltk::GREEN)),
+            HungerState::Normal => ("", RGB::named(rltk::WHITE)),
+            HungerState::Hungry => ("Hungry", RGB::named(rltk::ORANGE)),
+            HungerState::Starving => ("Starving", RGB::named(rltk::RED)),
+        };
+        ctx.print_color(
+            77 - label.len() as i32,
+            43,
+            colour,
+            RGB::named(rltk::BLACK),
+            label,
+        );
+    }
+
+    // Status effects sit along the top of the log box
+    for (_player, status) in (&players, &statuses).join() {
+        let mut x = 2;
+        for active in status.active.iter() {
+            let (label, colour) = active.effect.icon();
+            let label = format!("{} {}", label, active.turns);
+            ctx.print_color(x, 43, colour, RGB::named(rltk::BLACK), &label);
+            x += label.len() as i32 + 1;
+        }
+    }
+    let encumbrance = ecs.read_storage::<Encumbrance>();
+    let attributes = ecs.read_storage::<Attributes>();
+    for (_player, load, attr) in (&players, &encumbrance, &attributes).join() {
+        if load.carried > attr.carry_capacity() {
+            ctx.print_color(
+                60,
+                43,
+                RGB::named(rltk::ORANGE),
+                RGB::named(rltk::BLACK),
+                "Burdened",
+            );
+        }
+    }
+
+    let purses = ecs.read_storage::<Purse>();
+    for (_player, purse) in (&players, &purses).join() {
+        let gold = format!("{} gold", purse.gold);
+        ctx.print_color(
+            2,
+            49,
+            RGB::named(rltk::GOLD),
+            RGB::named(rltk::BLACK),
+            &gold,
+        );
+    }
+
+    let clock = ecs.fetch::<GameClock>();
+    let turn = format!("Turn {}", clock.turn);
+    ctx.print_color(
+        77 - turn.len() as i32,
+        49,
+        RGB::named(rltk::GREY),
+        RGB::named(rltk::BLACK),
+        &turn,
+    );
+
     let log = ecs.fetch::<GameLog>();
     let mut y = 44;
     for s in log.entries.iter().rev() {
@@ -171,10 +252,50 @@ fn draw_tooltips(ecs: &World, ctx: &mut Rltk) {
         }
     }
 }
+/// Prints what the player is carrying against what they can carry comfortably,
+/// right-aligned to end at `right`.
+fn print_carried_weight(ecs: &World, ctx: &mut Rltk, right: i32, y: i32) {
+    let player_entity = ecs.fetch::<Entity>();
+    let encumbrance = ecs.read_storage::<Encumbrance>();
+    let attributes = ecs.read_storage::<Attributes>();
+    if let (Some(load), Some(attr)) = (
+        encumbrance.get(*player_entity),
+        attributes.get(*player_entity),
+    ) {
+        let colour = if load.carried > attr.carry_capacity() {
+            RGB::named(rltk::ORANGE)
+        } else {
+            RGB::named(rltk::WHITE)
+        };
+        let label = format!("{:.1}/{} lb", load.carried, attr.carry_capacity());
+        ctx.print_color(
+            right - label.len() as i32,
+            y,
+            colour,
+            RGB::named(rltk::BLACK),
+            &label,
+        );
+    }
+}
+
+/// How an item reads in a menu, e.g. "3x Ketchup" for a stack.
+fn item_label(name: &Name, stack: Option<&Stackable>, charges: Option<&Charges>) -> String {
+    let label = match stack {
+        Some(stack) if stack.quantity > 1 => format!("{}x {}", stack.quantity, name.name),
+        _ => name.name.to_string(),
+    };
+    match charges {
+        Some(charges) => format!("{} ({}/{})", label, charges.remaining, charges.max),
+        None => label,
+    }
+}
+
 pub fn show_inventory(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Option<Entity>) {
     ctx.set_active_console(2);
     let player_entity = gs.ecs.fetch::<Entity>();
     let names = gs.ecs.read_storage::<Name>();
+    let stacks = gs.ecs.read_storage::<Stackable>();
+    let charges = gs.ecs.read_storage::<Charges>();
     let backpack = gs.ecs.read_storage::<InBackpack>();
     let entities = gs.ecs.entities();
 
@@ -199,6 +320,7 @@ pub fn show_inventory(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Option
         RGB::named(rltk::BLACK),
         "Inventory",
     );
+    print_carried_weight(&gs.ecs, ctx, 45, y - 2);
     ctx.print_color(
         18,
         y + count as i32 + 1,
@@ -234,7 +356,11 @@ pub fn show_inventory(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Option
             rltk::to_cp437(')'),
         );
 
-        ctx.print(21, y, &name.name.to_string());
+        ctx.print(
+            21,
+            y,
+            item_label(name, stacks.get(entity), charges.get(entity)),
+        );
         equippable.push(entity);
         y += 1;
         j += 1;
@@ -259,15 +385,51 @@ pub fn show_inventory(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Option
 }
 
 pub fn drop_item_menu(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Option<Entity>) {
+    backpack_menu(&gs.ecs, ctx, "Drop Which Item?", |_item| true)
+}
+
+pub fn throw_item_menu(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Option<Entity>) {
+    backpack_menu(&gs.ecs, ctx, "Throw Which Item?", |_item| true)
+}
+
+/// Lists the player's unidentified items and gear that might be cursed, other
+/// than the identify scroll being read.
+pub fn identify_item_menu(
+    gs: &mut State,
+    ctx: &mut Rltk,
+    scroll: Entity,
+) -> (ItemMenuResult, Option<Entity>) {
+    let unidentified = gs.ecs.read_storage::<Unidentified>();
+    let equippable = gs.ecs.read_storage::<Equippable>();
+    let cursed = gs.ecs.read_storage::<Cursed>();
+    let known_clean = gs.ecs.read_storage::<KnownClean>();
+    backpack_menu(&gs.ecs, ctx, "Identify Which Item?", |item| {
+        let unknown_gear = equippable.get(item).is_some()
+            && !cursed.get(item).is_some_and(|c| c.known)
+            && known_clean.get(item).is_none();
+        item != scroll && (unidentified.get(item).is_some() || unknown_gear)
+    })
+}
+
+/// Lists the backpack items passing `include` under `title` and returns whichever
+/// item the player picks.
+fn backpack_menu<F: Fn(Entity) -> bool>(
+    ecs: &World,
+    ctx: &mut Rltk,
+    title: &str,
+    include: F,
+) -> (ItemMenuResult, Option<Entity>) {
     ctx.set_active_console(2);
-    let player_entity = gs.ecs.fetch::<Entity>();
-    let names = gs.ecs.read_storage::<Name>();
-    let backpack = gs.ecs.read_storage::<InBackpack>();
-    let entities = gs.ecs.entities();
+    let player_entity = ecs.fetch::<Entity>();
+    let names = ecs.read_storage::<Name>();
+    let stacks = ecs.read_storage::<Stackable>();
+    let charges = ecs.read_storage::<Charges>();
+    let backpack = ecs.read_storage::<InBackpack>();
+    let entities = ecs.entities();
 
-    let inventory = (&backpack, &names)
+    let inventory = (&entities, &backpack, &names)
         .join()
-        .filter(|item| item.0.owner == *player_entity);
+        .filter(|item| item.1.owner == *player_entity && include(item.0));
     let count = inventory.count();
 
     let mut y = (25 - (count / 2)) as i32;
@@ -284,8 +446,9 @@ pub fn drop_item_menu(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Option
         y - 2,
         RGB::named(rltk::YELLOW),
         RGB::named(rltk::BLACK),
-        "Drop Which Item?",
+        title,
     );
+    print_carried_weight(ecs, ctx, 45, y - 2);
     ctx.print_color(
         18,
         y + count as i32 + 1,
@@ -298,7 +461,7 @@ pub fn drop_item_menu(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Option
     let mut j = 0;
     for (entity, _pack, name) in (&entities, &backpack, &names)
         .join()
-        .filter(|item| item.1.owner == *player_entity)
+        .filter(|item| item.1.owner == *player_entity && include(item.0))
     {
         ctx.set(
             17,
@@ -322,7 +485,11 @@ pub fn drop_item_menu(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Option
             rltk::to_cp437(')'),
         );
 
-        ctx.print(21, y, &name.name.to_string());
+        ctx.print(
+            21,
+            y,
+            item_label(name, stacks.get(entity), charges.get(entity)),
+        );
         equippable.push(entity);
         y += 1;
         j += 1;
@@ -565,48 +732,49 @@ pub fn main_menu(gs: &mut State, ctx: &mut Rltk) -> MainMenuResult {
     }
 }
 
-pub fn remove_item_menu(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Option<Entity>) {
+/// Shows what the player is wearing in each equipment slot. Picking a filled slot
+/// returns the item so it can be taken off.
+pub fn equipment_menu(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Option<Entity>) {
     ctx.set_active_console(2);
     let player_entity = gs.ecs.fetch::<Entity>();
     let names = gs.ecs.read_storage::<Name>();
-    let backpack = gs.ecs.read_storage::<Equipped>();
+    let equipped = gs.ecs.read_storage::<Equipped>();
+    let two_handed = gs.ecs.read_storage::<TwoHanded>();
     let entities = gs.ecs.entities();
 
-    let inventory = (&backpack, &names)
+    let worn: Vec<(Entity, &Equipped, &Name)> = (&entities, &equipped, &names)
         .join()
-        .filter(|item| item.0.owner == *player_entity);
-    let count = inventory.count();
+        .filter(|item| item.1.owner == *player_entity)
+        .collect();
+    let count = EQUIPMENT_SLOTS.len();
 
-    let mut y = (25 - (count / 2)) as i32;
+    let top = (25 - (count / 2)) as i32;
     ctx.draw_box(
         15,
-        y - 2,
-        31,
+        top - 2,
+        40,
         (count + 3) as i32,
         RGB::named(rltk::WHITE),
         RGB::named(rltk::BLACK),
     );
     ctx.print_color(
         18,
-        y - 2,
+        top - 2,
         RGB::named(rltk::YELLOW),
         RGB::named(rltk::BLACK),
-        "Remove Which Item?",
+        "Equipment (remove which?)",
     );
     ctx.print_color(
         18,
-        y + count as i32 + 1,
+        top + count as i32 + 1,
         RGB::named(rltk::YELLOW),
         RGB::named(rltk::BLACK),
         "ESCAPE to cancel",
     );
 
-    let mut equippable: Vec<Entity> = Vec::new();
-    let mut j = 0;
-    for (entity, _pack, name) in (&entities, &backpack, &names)
-        .join()
-        .filter(|item| item.1.owner == *player_entity)
-    {
+    let mut in_slot: Vec<Option<Entity>> = Vec::new();
+    for (j, slot) in EQUIPMENT_SLOTS.iter().enumerate() {
+        let y = top + j as i32;
         ctx.set(
             17,
             y,
@@ -628,11 +796,40 @@ pub fn remove_item_menu(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Opti
             RGB::named(rltk::BLACK),
             rltk::to_cp437(')'),
         );
+        ctx.print_color(
+            21,
+            y,
+            RGB::named(rltk::GREY),
+            RGB::named(rltk::BLACK),
+            format!("{}:", slot.name()),
+        );
 
-        ctx.print(21, y, &name.name.to_string());
-        equippable.push(entity);
-        y += 1;
-        j += 1;
+        let item = worn.iter().find(|(_e, eq, _name)| eq.slot == *slot);
+        let both_paws = worn.iter().find(|(e, eq, _name)| {
+            *slot == EquipmentSlot::Shield
+                && eq.slot == EquipmentSlot::Melee
+                && two_handed.get(*e).is_some()
+        });
+        match (item, both_paws) {
+            (Some((entity, _eq, name)), _) => {
+                ctx.print(29, y, &name.name);
+                in_slot.push(Some(*entity));
+            }
+            (None, Some((_e, _eq, name))) => {
+                ctx.print_color(
+                    29,
+                    y,
+                    RGB::named(rltk::GREY),
+                    RGB::named(rltk::BLACK),
+                    format!("({})", name.name),
+                );
+                in_slot.push(None);
+            }
+            (None, None) => {
+                ctx.print_color(29, y, RGB::named(rltk::GREY), RGB::named(rltk::BLACK), "-");
+                in_slot.push(None);
+            }
+        }
     }
 
     match ctx.key {
@@ -642,10 +839,91 @@ pub fn remove_item_menu(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Opti
             _ => {
                 let selection = rltk::letter_to_option(key);
                 if selection > -1 && selection < count as i32 {
-                    return (
-                        ItemMenuResult::Selected,
-                        Some(equippable[selection as usize]),
-                    );
+                    if let Some(item) = in_slot[selection as usize] {
+                        return (ItemMenuResult::Selected, Some(item));
+                    }
+                }
+                (ItemMenuResult::NoResponse, None)
+            }
+        },
+    }
+}
+
+/// Lists every recipe in the cookbook, greying out the ones the player can't make
+/// right now, and returns the index of the one they pick.
+pub fn cook_menu(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Option<usize>) {
+    ctx.set_active_console(2);
+    let cookbook = gs.ecs.fetch::<Cookbook>();
+    let count = cookbook.recipes.len();
+
+    let top = (25 - (count / 2)) as i32;
+    ctx.draw_box(
+        10,
+        top - 2,
+        60,
+        (count + 3) as i32,
+        RGB::named(rltk::WHITE),
+        RGB::named(rltk::BLACK),
+    );
+    ctx.print_color(
+        13,
+        top - 2,
+        RGB::named(rltk::YELLOW),
+        RGB::named(rltk::BLACK),
+        "Cook What?",
+    );
+    ctx.print_color(
+        13,
+        top + count as i32 + 1,
+        RGB::named(rltk::YELLOW),
+        RGB::named(rltk::BLACK),
+        "ESCAPE to cancel",
+    );
+
+    for (j, recipe) in cookbook.recipes.iter().enumerate() {
+        let y = top + j as i32;
+        let fg = if cookbook::can_cook(&gs.ecs, recipe) {
+            RGB::named(rltk::WHITE)
+        } else {
+            RGB::named(rltk::GRAY)
+        };
+        ctx.set(
+            12,
+            y,
+            RGB::named(rltk::WHITE),
+            RGB::named(rltk::BLACK),
+            rltk::to_cp437('('),
+        );
+        ctx.set(
+            13,
+            y,
+            RGB::named(rltk::YELLOW),
+            RGB::named(rltk::BLACK),
+            97 + j as rltk::FontCharType,
+        );
+        ctx.set(
+            14,
+            y,
+            RGB::named(rltk::WHITE),
+            RGB::named(rltk::BLACK),
+            rltk::to_cp437(')'),
+        );
+
+        let mut description = format!("{}: {}", recipe.name, recipe.ingredients.join(", "));
+        if recipe.needs_stove {
+            description += " [stove]";
+        }
+        ctx.print_color(16, y, fg, RGB::named(rltk::BLACK), description);
+    }
+
+    match ctx.key {
+        None => (ItemMenuResult::NoResponse, None),
+        Some(key) => match key {
+            VirtualKeyCode::Escape => (ItemMenuResult::Cancel, None),
+            _ => {
+                let selection = rltk::letter_to_option(key);
+                if selection > -1 && selection < count as i32 {
+                    return (ItemMenuResult::Selected, Some(selection as usize));
                 }
                 (ItemMenuResult::NoResponse, None)
             }
@@ -653,13 +931,184 @@ pub fn remove_item_menu(gs: &mut State, ctx: &mut Rltk) -> (ItemMenuResult, Opti
     }
 }
 
+#[derive(PartialEq, Copy, Clone)]
+pub enum ShopMenuResult {
+    Cancel,
+    NoResponse,
+    SwitchMode,
+    Selected,
+}
+
+/// Lists what the vendor has for sale, or what the player could sell them, with
+/// prices. TAB flips between buying and selling.
+pub fn shop_menu(
+    gs: &mut State,
+    ctx: &mut Rltk,
+    vendor: Entity,
+    selling: bool,
+) -> (ShopMenuResult, Option<Entity>) {
+    ctx.set_active_console(2);
+    let player_entity = gs.ecs.fetch::<Entity>();
+    let names = gs.ecs.read_storage::<Name>();
+    let stacks = gs.ecs.read_storage::<Stackable>();
+    let charges = gs.ecs.read_storage::<Charges>();
+    let backpack = gs.ecs.read_storage::<InBackpack>();
+    let purses = gs.ecs.read_storage::<Purse>();
+    let entities = gs.ecs.entities();
+
+    let owner = if selling { *player_entity } else { vendor };
+    let goods: Vec<(Entity, &Name)> = (&entities, &backpack, &names)
+        .join()
+        .filter(|(_e, pack, _name)| pack.owner == owner)
+        .map(|(e, _pack, name)| (e, name))
+        .collect();
+    let count = goods.len();
+
+    let top = (25 - (count / 2)) as i32;
+    ctx.draw_box(
+        10,
+        top - 2,
+        50,
+        (count + 3) as i32,
+        RGB::named(rltk::WHITE),
+        RGB::named(rltk::BLACK),
+    );
+    let title = format!(
+        "{}: {}",
+        names.get(vendor).unwrap().name,
+        if selling { "Selling" } else { "Buying" }
+    );
+    ctx.print_color(
+        13,
+        top - 2,
+        RGB::named(rltk::YELLOW),
+        RGB::named(rltk::BLACK),
+        title,
+    );
+    let gold = format!("{} gold", purses.get(*player_entity).map_or(0, |p| p.gold));
+    ctx.print_color(
+        58 - gold.len() as i32,
+        top - 2,
+        RGB::named(rltk::GOLD),
+        RGB::named(rltk::BLACK),
+        gold,
+    );
+    ctx.print_color(
+        13,
+        top + count as i32 + 1,
+        RGB::named(rltk::YELLOW),
+        RGB::named(rltk::BLACK),
+        "TAB to buy/sell, ESCAPE to leave",
+    );
+
+    for (j, (entity, name)) in goods.iter().enumerate() {
+        let y = top + j as i32;
+        ctx.set(
+            12,
+            y,
+            RGB::named(rltk::WHITE),
+            RGB::named(rltk::BLACK),
+            rltk::to_cp437('('),
+        );
+        ctx.set(
+            13,
+            y,
+            RGB::named(rltk::YELLOW),
+            RGB::named(rltk::BLACK),
+            97 + j as rltk::FontCharType,
+        );
+        ctx.set(
+            14,
+            y,
+            RGB::named(rltk::WHITE),
+            RGB::named(rltk::BLACK),
+            rltk::to_cp437(')'),
+        );
+
+        ctx.print(
+            16,
+            y,
+            item_label(name, stacks.get(*entity), charges.get(*entity)),
+        );
+        let price = if selling {
+            shop::sell_price(&gs.ecs, *entity)
+        } else {
+            shop::buy_price(&gs.ecs, *entity)
+        };
+        let price = format!("{}g", price);
+        ctx.print_color(
+            58 - price.len() as i32,
+            y,
+            RGB::named(rltk::GOLD),
+            RGB::named(rltk::BLACK),
+            price,
+        );
+    }
+
+    match ctx.key {
+        None => (ShopMenuResult::NoResponse, None),
+        Some(key) => match key {
+            VirtualKeyCode::Escape => (ShopMenuResult::Cancel, None),
+            VirtualKeyCode::Tab => (ShopMenuResult::SwitchMode, None),
+            _ => {
+                let selection = rltk::letter_to_option(key);
+                if selection > -1 && selection < count as i32 {
+                    return (ShopMenuResult::Selected, Some(goods[selection as usize].0));
+                }
+                (ShopMenuResult::NoResponse, None)
+            }
+        },
+    }
+}
+
+#[derive(PartialEq, Copy, Clone)]
+pub enum LevelUpChoice {
+    Health,
+    Power,
+    Defense,
+}
+
+pub fn level_up_menu(ctx: &mut Rltk) -> Option<LevelUpChoice> {
+    ctx.set_active_console(2);
+    ctx.draw_box(
+        15,
+        15,
+        50,
+        8,
+        RGB::named(rltk::WHITE),
+        RGB::named(rltk::BLACK),
+    );
+    ctx.print_color(
+        18,
+        15,
+        RGB::named(rltk::YELLOW),
+        RGB::named(rltk::BLACK),
+        "Level up! Choose an improvement",
+    );
+    let choices = [
+        "(a) Hardier: +10 max hp",
+        "(b) Stronger: +1 power",
+        "(c) Tougher: +1 defense",
+    ];
+    for (i, choice) in choices.iter().enumerate() {
+        ctx.print(17, 17 + i as i32 * 2, choice);
+    }
+
+    match ctx.key {
+        Some(VirtualKeyCode::A) => Some(LevelUpChoice::Health),
+        Some(VirtualKeyCode::B) => Some(LevelUpChoice::Power),
+        Some(VirtualKeyCode::C) => Some(LevelUpChoice::Defense),
+        _ => None,
+    }
+}
+
 #[derive(PartialEq, Copy, Clone)]
 pub enum GameOverResult {
     NoSelection,
     QuitToMenu,
 }
 
-pub fn game_over(ctx: &mut Rltk) -> GameOverResult {
+pub fn game_over(ecs: &World, ctx: &mut Rltk) -> GameOverResult {
     ctx.set_active_console(2);
     ctx.print_color_centered(
         15,
@@ -667,6 +1116,16 @@ pub fn game_over(ctx: &mut Rltk) -> GameOverResult {
         RGB::named(rltk::BLACK),
         "You're out of ketchup!",
     );
+
+    let player_entity = ecs.fetch::<Entity>();
+    let killed_by = ecs.read_storage::<KilledBy>();
+    let depth = ecs.fetch::<Map>().depth;
+    let cause = match killed_by.get(*player_entity) {
+        None => format!("You died on depth {}.", depth),
+        Some(killed_by) => format!("Killed by {} on depth {}.", killed_by.cause, depth),
+    };
+    ctx.print_color_centered(16, RGB::named(rltk::RED), RGB::named(rltk::BLACK), &cause);
+
     ctx.print_color_centered(
         17,
         RGB::named(rltk::WHITE),
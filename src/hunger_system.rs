@@ -0,0 +1,80 @@
+use super::{
+    DamageType, GameLog, HungerClock, HungerState, Player, RunState, StatusEffect, StatusEffects,
+    SufferDamage,
+};
+use specs::prelude::*;
+
+pub const WELL_FED_TURNS: i32 = 20;
+pub const NORMAL_TURNS: i32 = 300;
+const HUNGRY_TURNS: i32 = 200;
+const STARVATION_DAMAGE: i32 = 1;
+
+pub struct HungerSystem {}
+
+impl<'a> System<'a> for HungerSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        ReadExpect<'a, RunState>,
+        WriteExpect<'a, GameLog>,
+        Entities<'a>,
+        WriteStorage<'a, HungerClock>,
+        ReadStorage<'a, Player>,
+        WriteStorage<'a, SufferDamage>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (runstate, mut log, entities, mut hunger_clocks, players, mut damage) = data;
+
+        // Only the player gets hungry, so this ticks on the player's turn
+        if *runstate != RunState::PlayerTurn {
+            return;
+        }
+
+        for (entity, clock, _player) in (&entities, &mut hunger_clocks, &players).join() {
+            clock.duration -= 1;
+            if clock.duration > 0 {
+                continue;
+            }
+
+            match clock.state {
+                HungerState::Normal => {
+                    clock.state = HungerState::Hungry;
+                    clock.duration = HUNGRY_TURNS;
+                    log.entries.push("Your tummy rumbles.".to_string());
+                }
+                HungerState::Hungry => {
+                    clock.state = HungerState::Starving;
+                    clock.duration = 0;
+                    log.entries
+                        .push("You are starving! Find something to eat!".to_string());
+                }
+                HungerState::Starving => {
+                    SufferDamage::new_damage(
+                        &mut damage,
+                        entity,
+                        STARVATION_DAMAGE,
+                        DamageType::Hunger,
+                        entity,
+                        None,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Fills the belly right up, whatever state it was in, and leaves the eater well
+/// fed for a while.
+pub fn eat(clock: &mut HungerClock, statuses: &mut WriteStorage<StatusEffects>, eater: Entity) {
+    clock.state = HungerState::Normal;
+    clock.duration = WELL_FED_TURNS + NORMAL_TURNS;
+    if let Some(effects) = statuses.get_mut(eater) {
+        effects.add(StatusEffect::WellFed, WELL_FED_TURNS, None, None);
+    } else {
+        let mut effects = StatusEffects::default();
+        effects.add(StatusEffect::WellFed, WELL_FED_TURNS, None, None);
+        statuses
+            .insert(eater, effects)
+            .expect("Unable to insert status");
+    }
+}
@@ -0,0 +1,144 @@
+use super::{Name, Unidentified};
+use rltk::{RandomNumberGenerator, RGB};
+use serde::{Deserialize, Serialize};
+use specs::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+const MAGIC_SCROLLS: [&str; 5] = [
+    "Fireball Scroll",
+    "Food Coma Scroll",
+    "Meat Beam Scroll",
+    "Identify Scroll",
+    "Remove Curse Scroll",
+];
+const MAGIC_SAUCES: [&str; 2] = ["Hot Sauce", "Chicken Soup"];
+const MAGIC_WANDS: [&str; 2] = ["Wand of Meat Beams", "Wand of Food Coma"];
+
+const SCROLL_WORDS: [&str; 16] = [
+    "BLORP", "ZESTA", "MUNGO", "FRAZZLE", "GLOOP", "WOBBLE", "KRUMB", "SNORF", "QUIBBLE", "TANGO",
+    "FLUMP", "YUMYUM", "BORK", "SPLOOT", "ZOOMIE", "NOMNOM",
+];
+const SCROLL_COLOURS: [(u8, u8, u8); 8] = [
+    (255, 255, 255),
+    (120, 180, 255),
+    (255, 215, 0),
+    (255, 150, 200),
+    (140, 240, 140),
+    (255, 160, 60),
+    (0, 230, 230),
+    (190, 130, 255),
+];
+// How each unidentified sauce and wand looks, and the colour it's drawn in.
+const SAUCE_LOOKS: [(&str, (u8, u8, u8)); 8] = [
+    ("Murky", (130, 110, 70)),
+    ("Fizzy", (200, 230, 255)),
+    ("Lumpy", (190, 150, 90)),
+    ("Glowing", (180, 255, 120)),
+    ("Chunky", (210, 90, 40)),
+    ("Smoky", (150, 150, 150)),
+    ("Sticky", (230, 180, 40)),
+    ("Cloudy", (235, 235, 235)),
+];
+const WAND_LOOKS: [(&str, (u8, u8, u8)); 6] = [
+    ("Licorice", (200, 30, 30)),
+    ("Breadstick", (230, 200, 140)),
+    ("Cinnamon", (180, 100, 50)),
+    ("Pretzel", (160, 110, 40)),
+    ("Celery", (150, 220, 120)),
+    ("Candy Cane", (255, 120, 120)),
+];
+
+// Which magic items the player has learned this run, and what the others look
+// like until they do. Saved with the game.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Identification {
+    pub aliases: HashMap<String, String>,
+    pub known: HashSet<String>,
+    pub colours: HashMap<String, (u8, u8, u8)>,
+}
+
+impl Identification {
+    /// Deals out a fresh set of unidentified names, shuffled by the run's seed.
+    pub fn new(seed: u64) -> Identification {
+        let mut rng = RandomNumberGenerator::seeded(seed);
+        let mut words = SCROLL_WORDS.to_vec();
+        shuffle(&mut words, &mut rng);
+        let mut scroll_colours = SCROLL_COLOURS.to_vec();
+        shuffle(&mut scroll_colours, &mut rng);
+        let mut looks = SAUCE_LOOKS.to_vec();
+        shuffle(&mut looks, &mut rng);
+        let mut wands = WAND_LOOKS.to_vec();
+        shuffle(&mut wands, &mut rng);
+
+        let mut aliases = HashMap::new();
+        let mut colours = HashMap::new();
+        for (i, scroll) in MAGIC_SCROLLS.iter().enumerate() {
+            aliases.insert(
+                scroll.to_string(),
+                format!("Scroll titled {} {}", words[i * 2], words[i * 2 + 1]),
+            );
+            colours.insert(scroll.to_string(), scroll_colours[i]);
+        }
+        for (sauce, (look, colour)) in MAGIC_SAUCES.iter().zip(looks.iter()) {
+            aliases.insert(sauce.to_string(), format!("{} Sauce", look));
+            colours.insert(sauce.to_string(), *colour);
+        }
+        for (wand, (look, colour)) in MAGIC_WANDS.iter().zip(wands.iter()) {
+            aliases.insert(wand.to_string(), format!("{} Wand", look));
+            colours.insert(wand.to_string(), *colour);
+        }
+
+        Identification {
+            aliases,
+            known: HashSet::new(),
+            colours,
+        }
+    }
+
+    /// The name an item goes by, which is its real one once the player knows it.
+    pub fn shown_name(&self, real_name: &str) -> String {
+        match self.aliases.get(real_name) {
+            Some(alias) if !self.known.contains(real_name) => alias.clone(),
+            _ => real_name.to_string(),
+        }
+    }
+
+    /// The colour an item is drawn in this run, so its looks don't give it away either.
+    pub fn colour(&self, real_name: &str) -> RGB {
+        match self.colours.get(real_name) {
+            Some((r, g, b)) => RGB::from_u8(*r, *g, *b),
+            None => RGB::named(rltk::WHITE),
+        }
+    }
+}
+
+fn shuffle<T>(items: &mut [T], rng: &mut RandomNumberGenerator) {
+    for i in (1..items.len()).rev() {
+        let j = rng.range(0, i + 1);
+        items.swap(i, j);
+    }
+}
+
+/// Learns what `real_name` items are, and gives every one of them in the world
+/// its real name back.
+pub fn identify(
+    identification: &mut Identification,
+    entities: &Entities,
+    names: &mut WriteStorage<Name>,
+    unidentified: &mut WriteStorage<Unidentified>,
+    real_name: &str,
+) {
+    identification.known.insert(real_name.to_string());
+
+    let revealed: Vec<Entity> = (entities, &*unidentified)
+        .join()
+        .filter(|(_e, unknown)| unknown.real_name == real_name)
+        .map(|(e, _unknown)| e)
+        .collect();
+    for item in revealed {
+        unidentified.remove(item);
+        if let Some(name) = names.get_mut(item) {
+            name.name = real_name.to_string();
+        }
+    }
+}
@@ -1,9 +1,20 @@
 use super::{
-    gamelog::GameLog, AreaOfEffect, CombatStats, Confusion, Consumable, Equippable, Equipped,
-    InBackpack, InflictsDamage, Map, Name, Position, ProvidesHealing, SufferDamage,
-    WantsToDropItem, WantsToPickupItem, WantsToRemoveItem, WantsToUseItem,
+    attr_bonus,
+    gamelog::GameLog,
+    hunger_system::eat,
+    identification::{identify, Identification},
+    noise_system::{NoiseEvents, CLATTER_VOLUME, EXPLOSION_VOLUME},
+    Ammunition, AreaOfEffect, Attributes, Charges, CombatStats, Consumable, Cursed, DamageType,
+    DefenseBonus, Encumbrance, EquipmentSlot, Equippable, Equipped, Fragile, Gold, HungerClock,
+    IdentifiesItem, InBackpack, InflictsDamage, InflictsStatus, Ingredient, Item, KnownClean, Map,
+    MeleePowerBonus, MeleeWeapon, Name, Position, ProvidesFood, ProvidesHealing, Purse, Ranged,
+    RangedWeapon, RemovesCurse, Renderable, SerializeMe, Skills, Stackable, StatusEffects,
+    SufferDamage, ThrowDamage, TileType, TwoHanded, Unidentified, Value, WantsToDropItem,
+    WantsToPickupItem, WantsToRemoveItem, WantsToThrowItem, WantsToUseItem, WeaponAffix, Weight,
 };
+use rltk::{LineAlg, Point, RandomNumberGenerator};
 use specs::prelude::*;
+use specs::saveload::{MarkedBuilder, SimpleMarker};
 
 pub struct ItemCollectionSystem {}
 
@@ -12,25 +23,114 @@ impl<'a> System<'a> for ItemCollectionSystem {
     type SystemData = (
         ReadExpect<'a, Entity>,
         WriteExpect<'a, GameLog>,
+        ReadExpect<'a, Map>,
         WriteStorage<'a, WantsToPickupItem>,
         WriteStorage<'a, Position>,
         ReadStorage<'a, Name>,
         WriteStorage<'a, InBackpack>,
+        Entities<'a>,
+        WriteStorage<'a, Stackable>,
+        ReadStorage<'a, Weight>,
+        ReadStorage<'a, Attributes>,
+        WriteStorage<'a, Encumbrance>,
+        ReadStorage<'a, Gold>,
+        WriteStorage<'a, Purse>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (player_entity, mut gamelog, mut wants_pickup, mut positions, names, mut backpack) =
-            data;
+        let (
+            player_entity,
+            mut gamelog,
+            map,
+            mut wants_pickup,
+            mut positions,
+            names,
+            mut backpack,
+            entities,
+            mut stacks,
+            weights,
+            attributes,
+            mut encumbrance,
+            gold_piles,
+            mut purses,
+        ) = data;
         for pickup in wants_pickup.join() {
+            // Gold goes straight in the purse
+            if let (Some(gold), Some(purse)) = (
+                gold_piles.get(pickup.item),
+                purses.get_mut(pickup.collected_by),
+            ) {
+                purse.gold += gold.amount;
+                if pickup.collected_by == *player_entity {
+                    gamelog
+                        .entries
+                        .push(format!("You pick up {} gold.", gold.amount));
+                }
+                entities.delete(pickup.item).expect("Delete failed");
+                continue;
+            }
+
+            // Nobody can pick up more than they can lift
+            let item_weight = weights.get(pickup.item).map_or(0.0, |w| w.pounds)
+                * stacks.get(pickup.item).map_or(1, |s| s.quantity) as f32;
+            if let (Some(load), Some(attr)) = (
+                encumbrance.get_mut(pickup.collected_by),
+                attributes.get(pickup.collected_by),
+            ) {
+                if load.carried + item_weight > attr.max_load() {
+                    if pickup.collected_by == *player_entity {
+                        gamelog.entries.push(format!(
+                            "The {} is too heavy for you to carry.",
+                            names.get(pickup.item).unwrap().name
+                        ));
+                    }
+                    continue;
+                }
+                load.carried += item_weight;
+            }
+
+            if pickup.collected_by != *player_entity {
+                if let Some(pos) = positions.get(pickup.item) {
+                    if map.visible_tiles[map.xy_idx(pos.x, pos.y)] {
+                        gamelog.entries.push(format!(
+                            "{} picks up the {}.",
+                            names.get(pickup.collected_by).unwrap().name,
+                            names.get(pickup.item).unwrap().name
+                        ));
+                    }
+                }
+            }
             positions.remove(pickup.item);
-            backpack
-                .insert(
-                    pickup.item,
-                    InBackpack {
-                        owner: pickup.collected_by,
-                    },
-                )
-                .expect("Unable to insert backpack entry");
+
+            // Stackable items join any matching stack we're already carrying
+            let mut merged = false;
+            if let Some(picked_up) = stacks.get(pickup.item).cloned() {
+                let item_name = &names.get(pickup.item).unwrap().name;
+                for (stack_entity, stack, pack, name) in
+                    (&entities, &mut stacks, &backpack, &names).join()
+                {
+                    if pack.owner == pickup.collected_by
+                        && name.name == *item_name
+                        && stack_entity != pickup.item
+                    {
+                        stack.quantity += picked_up.quantity;
+                        merged = true;
+                        break;
+                    }
+                }
+            }
+            if merged {
+                entities.delete(pickup.item).expect("Delete failed");
+            } else {
+                backpack
+                    .insert(
+                        pickup.item,
+                        InBackpack {
+                            owner: pickup.collected_by,
+                        },
+                    )
+                    .expect("Unable to insert backpack entry");
+            }
 
             if pickup.collected_by == *player_entity {
                 gamelog.entries.push(format!(
@@ -53,17 +153,32 @@ impl<'a> System<'a> for ItemUseSystem {
         ReadExpect<'a, Map>,
         Entities<'a>,
         WriteStorage<'a, WantsToUseItem>,
-        ReadStorage<'a, Name>,
-        ReadStorage<'a, Consumable>,
+        WriteStorage<'a, Name>,
+        (ReadStorage<'a, Consumable>, WriteStorage<'a, Charges>),
         ReadStorage<'a, ProvidesHealing>,
         ReadStorage<'a, InflictsDamage>,
         ReadStorage<'a, AreaOfEffect>,
         WriteStorage<'a, CombatStats>,
         WriteStorage<'a, SufferDamage>,
-        WriteStorage<'a, Confusion>,
-        ReadStorage<'a, Equippable>,
+        ReadStorage<'a, InflictsStatus>,
+        WriteStorage<'a, StatusEffects>,
+        (
+            ReadStorage<'a, Equippable>,
+            ReadStorage<'a, TwoHanded>,
+            WriteStorage<'a, Cursed>,
+            ReadStorage<'a, RemovesCurse>,
+        ),
         WriteStorage<'a, Equipped>,
         WriteStorage<'a, InBackpack>,
+        ReadStorage<'a, Position>,
+        WriteExpect<'a, NoiseEvents>,
+        ReadStorage<'a, Attributes>,
+        WriteStorage<'a, Skills>,
+        ReadStorage<'a, ProvidesFood>,
+        WriteStorage<'a, HungerClock>,
+        WriteStorage<'a, Unidentified>,
+        WriteExpect<'a, Identification>,
+        WriteStorage<'a, Stackable>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -73,23 +188,54 @@ impl<'a> System<'a> for ItemUseSystem {
             map,
             entities,
             mut wants_use_item,
-            names,
-            consumables,
+            mut names,
+            (consumables, mut charges),
             healing,
             inflict_damage,
             aoe,
             mut combat_stats,
             mut suffer_damage,
-            mut confused,
-            equippable,
+            inflicts_status,
+            mut status_effects,
+            (equippable, two_handed, mut cursed, removes_curse),
             mut equipped,
             mut backpack,
+            positions,
+            mut noise,
+            attributes,
+            mut skills,
+            provides_food,
+            mut hunger_clocks,
+            mut unidentified,
+            mut identification,
+            mut stacks,
         ) = data;
 
         for (entity, useitem) in (&entities, &wants_use_item).join() {
+            // Food and cooking magic work better in smart, practised paws
+            let cooking_bonus = attributes.get(entity).map_or(0, |a| attr_bonus(a.smarts))
+                + skills.get(entity).map_or(0, |s| s.cooking.level);
+            let mut cooked = false;
+
+            // Only narrate what other creatures do if the player can see them
+            let user_visible = positions
+                .get(entity)
+                .is_some_and(|pos| map.visible_tiles[map.xy_idx(pos.x, pos.y)]);
+
+            // A spent wand does nothing
+            if charges.get(useitem.item).is_some_and(|c| c.remaining < 1) {
+                if entity == *player_entity {
+                    gamelog.entries.push(format!(
+                        "The {} is out of charges.",
+                        names.get(useitem.item).unwrap().name
+                    ));
+                }
+                continue;
+            }
+
             let mut targets: Vec<Entity> = Vec::new();
             match useitem.target {
-                None => targets.push(*player_entity),
+                None => targets.push(entity),
                 Some(target) => {
                     let area_effect = aoe.get(useitem.item);
                     match area_effect {
@@ -102,6 +248,7 @@ impl<'a> System<'a> for ItemUseSystem {
                         }
                         Some(area_effect) => {
                             //AoE
+                            noise.make_noise(target.x, target.y, EXPLOSION_VOLUME);
                             let mut blast_tiles =
                                 rltk::field_of_view(target, area_effect.radius, &*map);
                             blast_tiles.retain(|p| {
@@ -124,25 +271,51 @@ impl<'a> System<'a> for ItemUseSystem {
                 Some(can_equip) => {
                     let target_slot = can_equip.slot;
                     let target = targets[0];
+                    let needs_both_paws = two_handed.get(useitem.item).is_some();
 
-                    // Remove any items the target has in the item's slot
-                    let mut to_unequip: Vec<Entity> = Vec::new();
-                    for (item_entity, already_equipped, name) in
-                        (&entities, &equipped, &names).join()
-                    {
-                        if already_equipped.owner == target && already_equipped.slot == target_slot
-                        {
-                            to_unequip.push(item_entity);
-                            if target == *player_entity {
-                                gamelog.entries.push(format!("You unequip {}.", name.name));
-                            }
+                    // Find anything the target has in the item's slot, and anything in
+                    // the way of (or that is) a two-handed weapon
+                    let in_the_way: Vec<Entity> = (&entities, &equipped)
+                        .join()
+                        .filter(|(item_entity, already_equipped)| {
+                            already_equipped.owner == target
+                                && (already_equipped.slot == target_slot
+                                    || (needs_both_paws
+                                        && already_equipped.slot == EquipmentSlot::Shield)
+                                    || (target_slot == EquipmentSlot::Shield
+                                        && two_handed.get(*item_entity).is_some()))
+                        })
+                        .map(|(item_entity, _already_equipped)| item_entity)
+                        .collect();
+
+                    // Cursed gear stays put
+                    let stuck = in_the_way.iter().find(|item| cursed.get(**item).is_some());
+                    if let Some(stuck) = stuck {
+                        if target == *player_entity {
+                            gamelog.entries.push(format!(
+                                "You can't take off the {}!",
+                                names.get(*stuck).unwrap().name
+                            ));
                         }
+                        continue;
                     }
-                    for item in to_unequip.iter() {
+
+                    for item in in_the_way.iter() {
+                        if target == *player_entity {
+                            gamelog
+                                .entries
+                                .push(format!("You unequip {}.", names.get(*item).unwrap().name));
+                        } else if user_visible {
+                            gamelog.entries.push(format!(
+                                "{} unequips {}.",
+                                names.get(target).unwrap().name,
+                                names.get(*item).unwrap().name
+                            ));
+                        }
                         equipped.remove(*item);
                         backpack
                             .insert(*item, InBackpack { owner: target })
-                            .expect(("Unable to insert item to backpack"));
+                            .expect("Unable to insert item to backpack");
                     }
 
                     // Wield the item
@@ -161,25 +334,89 @@ impl<'a> System<'a> for ItemUseSystem {
                             "You equip {}.",
                             names.get(useitem.item).unwrap().name
                         ));
+                    } else if user_visible {
+                        gamelog.entries.push(format!(
+                            "{} equips {}.",
+                            names.get(target).unwrap().name,
+                            names.get(useitem.item).unwrap().name
+                        ));
+                    }
+
+                    // Putting on something cursed is how you find out
+                    if let Some(curse) = cursed.get_mut(useitem.item) {
+                        if !curse.known && target == *player_entity {
+                            gamelog.entries.push(format!(
+                                "The {} is cursed! It won't come off.",
+                                names.get(useitem.item).unwrap().name
+                            ));
+                        }
+                        if let Some(name) = names.get_mut(useitem.item) {
+                            reveal_curse(curse, name);
+                        }
+                    }
+                }
+            }
+
+            // Lifting curses works on everything the target has on
+            if removes_curse.get(useitem.item).is_some() {
+                for target in targets.iter() {
+                    let lifted: Vec<Entity> = (&entities, &equipped, &cursed)
+                        .join()
+                        .filter(|(_item, eq, _curse)| eq.owner == *target)
+                        .map(|(item, _eq, _curse)| item)
+                        .collect();
+                    for item in lifted {
+                        cursed.remove(item);
+                        if let Some(name) = names.get_mut(item) {
+                            lift_curse(name);
+                            if *target == *player_entity {
+                                gamelog
+                                    .entries
+                                    .push(format!("The curse on the {} lifts.", name.name));
+                            }
+                        }
                     }
                 }
             }
 
             let mut used_item = true;
 
+            if provides_food.get(useitem.item).is_some() {
+                for target in targets.iter() {
+                    if let Some(hunger) = hunger_clocks.get_mut(*target) {
+                        eat(hunger, &mut status_effects, *target);
+                        if *target == *player_entity {
+                            gamelog.entries.push(format!(
+                                "You eat the {}. Delicious!",
+                                names.get(useitem.item).unwrap().name
+                            ));
+                        }
+                    }
+                }
+            }
+
             let item_heals = healing.get(useitem.item);
             match item_heals {
                 None => {}
                 Some(healer) => {
+                    cooked = true;
+                    let heal_amount = i32::max(1, healer.heal_amount + cooking_bonus);
                     for target in targets.iter() {
                         let stats = combat_stats.get_mut(*target);
                         if let Some(stats) = stats {
-                            stats.hp = i32::min(stats.max_hp, stats.hp + healer.heal_amount);
+                            stats.hp = i32::min(stats.max_hp, stats.hp + heal_amount);
                             if entity == *player_entity {
                                 gamelog.entries.push(format!(
                                     "You use the {}, healing {} hp.",
                                     names.get(useitem.item).unwrap().name,
-                                    healer.heal_amount
+                                    heal_amount
+                                ));
+                            } else if user_visible {
+                                gamelog.entries.push(format!(
+                                    "{} uses the {}, healing {} hp.",
+                                    names.get(entity).unwrap().name,
+                                    names.get(useitem.item).unwrap().name,
+                                    heal_amount
                                 ));
                             }
                         }
@@ -191,56 +428,149 @@ impl<'a> System<'a> for ItemUseSystem {
                 None => {}
                 Some(damage) => {
                     used_item = false;
+                    cooked = true;
+                    let damage_amount = i32::max(1, damage.damage + cooking_bonus);
                     for mob in targets.iter() {
-                        SufferDamage::new_damage(&mut suffer_damage, *mob, damage.damage);
+                        SufferDamage::new_damage(
+                            &mut suffer_damage,
+                            *mob,
+                            damage_amount,
+                            damage.damage_type,
+                            entity,
+                            Some(useitem.item),
+                        );
                         if entity == *player_entity {
                             let mob_name = names.get(*mob).unwrap();
                             let item_name = names.get(useitem.item).unwrap();
                             gamelog.entries.push(format!(
                                 "You use {} on {}, inflicting {} hp.",
-                                item_name.name, mob_name.name, damage.damage
+                                item_name.name, mob_name.name, damage_amount
+                            ));
+                        } else if user_visible && combat_stats.get(*mob).is_some() {
+                            let user_name = names.get(entity).unwrap();
+                            let mob_name = names.get(*mob).unwrap();
+                            let item_name = names.get(useitem.item).unwrap();
+                            gamelog.entries.push(format!(
+                                "{} uses {} on {}, inflicting {} hp.",
+                                user_name.name, item_name.name, mob_name.name, damage_amount
                             ));
                         }
                         used_item = true;
                     }
                 }
             }
-            // Confusion effect
-            let mut add_confusion = Vec::new();
-            {
-                let causes_confusion = confused.get(useitem.item);
-                match causes_confusion {
-                    None => {}
-                    Some(confusion) => {
-                        used_item = false;
-                        for mob in targets.iter() {
-                            add_confusion.push((*mob, confusion.turns));
-                            if entity == *player_entity {
-                                let mob_name = names.get(*mob).unwrap();
-                                let item_name = names.get(useitem.item).unwrap();
-                                gamelog.entries.push(format!(
-                                    "You use {} on {}, confusing them.",
-                                    item_name.name, mob_name.name
-                                ));
-                            }
-                            used_item = true;
-                        }
+            // Status effects
+            if let Some(status) = inflicts_status.get(useitem.item) {
+                used_item = false;
+                cooked = true;
+                let turns = i32::max(1, status.turns + cooking_bonus);
+                for mob in targets.iter() {
+                    if combat_stats.get(*mob).is_none() {
+                        continue;
+                    }
+                    if let Some(effects) = status_effects.get_mut(*mob) {
+                        effects.add(status.effect, turns, Some(entity), Some(useitem.item));
+                    } else {
+                        let mut effects = StatusEffects::default();
+                        effects.add(status.effect, turns, Some(entity), Some(useitem.item));
+                        status_effects
+                            .insert(*mob, effects)
+                            .expect("Unable to insert status");
+                    }
+
+                    let item_name = &names.get(useitem.item).unwrap().name;
+                    let effect_name = status.effect.name();
+                    if entity == *player_entity && *mob == *player_entity {
+                        gamelog.entries.push(format!(
+                            "You use the {}. You are now {}.",
+                            item_name, effect_name
+                        ));
+                    } else if entity == *player_entity {
+                        gamelog.entries.push(format!(
+                            "You use {} on {}, who is now {}.",
+                            item_name,
+                            names.get(*mob).unwrap().name,
+                            effect_name
+                        ));
+                    } else if *mob == *player_entity {
+                        gamelog.entries.push(format!(
+                            "{} uses {} on you. You are now {}!",
+                            names.get(entity).unwrap().name,
+                            item_name,
+                            effect_name
+                        ));
+                    } else if user_visible {
+                        gamelog.entries.push(format!(
+                            "{} uses {} on {}, who is now {}.",
+                            names.get(entity).unwrap().name,
+                            item_name,
+                            names.get(*mob).unwrap().name,
+                            effect_name
+                        ));
+                    }
+                    used_item = true;
+                }
+            }
+
+            if cooked {
+                if let Some(user_skills) = skills.get_mut(entity) {
+                    if user_skills.cooking.practise() && entity == *player_entity {
+                        gamelog.entries.push(format!(
+                            "Your cooking skill improves to {}.",
+                            user_skills.cooking.level
+                        ));
                     }
                 }
             }
-            for mob in add_confusion.iter() {
-                confused
-                    .insert(mob.0, Confusion { turns: mob.1 })
-                    .expect("Unable to insert status");
+
+            // Using a magic item shows the player what it really was
+            let real_name = unidentified
+                .get(useitem.item)
+                .map(|unknown| unknown.real_name.clone());
+            if let Some(real_name) = real_name {
+                if entity == *player_entity {
+                    gamelog.entries.push(format!(
+                        "You identify the {} as {}.",
+                        names.get(useitem.item).unwrap().name,
+                        real_name
+                    ));
+                    identify(
+                        &mut identification,
+                        &entities,
+                        &mut names,
+                        &mut unidentified,
+                        &real_name,
+                    );
+                }
             }
 
             if used_item {
+                if let Some(charge) = charges.get_mut(useitem.item) {
+                    charge.remaining -= 1;
+                    if charge.remaining < 1 {
+                        let item_name = &names.get(useitem.item).unwrap().name;
+                        if charge.crumbles {
+                            if entity == *player_entity {
+                                gamelog
+                                    .entries
+                                    .push(format!("The {} crumbles to dust.", item_name));
+                            }
+                            entities.delete(useitem.item).expect("Delete failed");
+                        } else if entity == *player_entity {
+                            gamelog
+                                .entries
+                                .push(format!("The {} is out of charges.", item_name));
+                        }
+                    }
+                }
+
                 let consumable = consumables.get(useitem.item);
                 match consumable {
                     None => {}
-                    Some(_) => {
-                        entities.delete(useitem.item).expect("Delete failed");
-                    }
+                    Some(_) => match stacks.get_mut(useitem.item) {
+                        Some(stack) if stack.quantity > 1 => stack.quantity -= 1,
+                        _ => entities.delete(useitem.item).expect("Delete failed"),
+                    },
                 }
             }
         }
@@ -248,6 +578,274 @@ impl<'a> System<'a> for ItemUseSystem {
     }
 }
 
+/// Marks a curse as known, and says so in the item's name.
+pub fn reveal_curse(curse: &mut Cursed, name: &mut Name) {
+    if !curse.known {
+        curse.known = true;
+        name.name = format!("Cursed {}", name.name);
+    }
+}
+
+/// Takes the mark of a lifted curse back off an item's name.
+pub fn lift_curse(name: &mut Name) {
+    if let Some(plain) = name.name.strip_prefix("Cursed ") {
+        name.name = plain.to_string();
+    }
+}
+
+/// Whether `who` can take on `pounds` more without going past the most they can
+/// lift, the same hard cap picking things up is held to. Anyone without a load to
+/// track can carry anything.
+pub fn can_carry(ecs: &World, who: Entity, pounds: f32) -> bool {
+    let encumbrance = ecs.read_storage::<Encumbrance>();
+    let attributes = ecs.read_storage::<Attributes>();
+    match (encumbrance.get(who), attributes.get(who)) {
+        (Some(load), Some(attr)) => pounds <= 0.0 || load.carried + pounds <= attr.max_load(),
+        _ => true,
+    }
+}
+
+/// Adds `pounds` (or takes them off, if negative) to what `who` is carrying, so
+/// several trades in a row are each checked against the up-to-date load.
+pub fn adjust_load(ecs: &World, who: Entity, pounds: f32) {
+    if let Some(load) = ecs.write_storage::<Encumbrance>().get_mut(who) {
+        load.carried += pounds;
+    }
+}
+
+macro_rules! copy_components {
+    ($ecs:expr, $from:expr, $to:expr, $( $type:ty ),*) => {
+        $(
+        let component = $ecs.read_storage::<$type>().get($from).cloned();
+        if let Some(component) = component {
+            $ecs.write_storage::<$type>()
+                .insert($to, component)
+                .expect("Unable to copy component");
+        }
+        )*
+    };
+}
+
+/// Takes `amount` items off a stack as a new entity of their own and returns it.
+/// Anything that isn't a bigger stack than that is returned untouched.
+pub fn split_stack(ecs: &mut World, item: Entity, amount: i32) -> Entity {
+    let quantity = ecs
+        .read_storage::<Stackable>()
+        .get(item)
+        .map_or(1, |stack| stack.quantity);
+    if quantity <= amount {
+        return item;
+    }
+
+    let split = ecs
+        .create_entity()
+        .with(Stackable { quantity: amount })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+    // Every component an item can carry, in the order components.rs declares them;
+    // anything left off comes out of the split as a blank copy.
+    copy_components!(
+        ecs,
+        item,
+        split,
+        Position,
+        Renderable,
+        ProvidesFood,
+        Ingredient,
+        Name,
+        Item,
+        InBackpack,
+        Consumable,
+        Weight,
+        Value,
+        Gold,
+        Charges,
+        Unidentified,
+        IdentifiesItem,
+        ProvidesHealing,
+        Ranged,
+        InflictsDamage,
+        AreaOfEffect,
+        InflictsStatus,
+        Cursed,
+        RemovesCurse,
+        KnownClean,
+        TwoHanded,
+        Equippable,
+        MeleePowerBonus,
+        MeleeWeapon,
+        WeaponAffix,
+        RangedWeapon,
+        Ammunition,
+        ThrowDamage,
+        Fragile,
+        DefenseBonus
+    );
+    if let Some(stack) = ecs.write_storage::<Stackable>().get_mut(item) {
+        stack.quantity -= amount;
+    }
+    split
+}
+
+/// How far the player can throw something.
+pub const THROW_RANGE: i32 = 6;
+
+pub struct ItemThrowSystem {}
+
+impl<'a> System<'a> for ItemThrowSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
+        ReadExpect<'a, Map>,
+        Entities<'a>,
+        WriteStorage<'a, WantsToThrowItem>,
+        ReadStorage<'a, Name>,
+        WriteStorage<'a, Position>,
+        WriteStorage<'a, InBackpack>,
+        ReadStorage<'a, CombatStats>,
+        ReadStorage<'a, ThrowDamage>,
+        ReadStorage<'a, Weight>,
+        ReadStorage<'a, Fragile>,
+        WriteStorage<'a, AreaOfEffect>,
+        WriteStorage<'a, WantsToUseItem>,
+        WriteStorage<'a, SufferDamage>,
+        ReadStorage<'a, Attributes>,
+        WriteExpect<'a, NoiseEvents>,
+        WriteExpect<'a, RandomNumberGenerator>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            player_entity,
+            mut gamelog,
+            map,
+            entities,
+            mut wants_throw,
+            names,
+            mut positions,
+            mut backpack,
+            combat_stats,
+            throw_damage,
+            weights,
+            fragile,
+            mut aoe,
+            mut wants_use,
+            mut suffer_damage,
+            attributes,
+            mut noise,
+            mut rng,
+        ) = data;
+
+        for (entity, to_throw) in (&entities, &wants_throw).join() {
+            let start = match positions.get(entity) {
+                Some(pos) => Point::new(pos.x, pos.y),
+                None => continue,
+            };
+            let is_player = entity == *player_entity;
+            let item_name = names.get(to_throw.item).unwrap().name.clone();
+            let thrower_attributes = attributes.get(entity).cloned().unwrap_or_default();
+
+            // Clumsy throws drift a tile off target
+            let mut target = to_throw.target;
+            let accuracy = rng.roll_dice(1, 20) + attr_bonus(thrower_attributes.agility);
+            if accuracy < 10 {
+                target.x += rng.roll_dice(1, 3) - 2;
+                target.y += rng.roll_dice(1, 3) - 2;
+            }
+
+            // Follow the line until something gets in the way
+            let mut landing = start;
+            let mut victim: Option<Entity> = None;
+            for tile in rltk::line2d(LineAlg::Bresenham, start, target)
+                .iter()
+                .skip(1)
+            {
+                if tile.x < 1 || tile.x > map.width - 2 || tile.y < 1 || tile.y > map.height - 2 {
+                    break;
+                }
+                let idx = map.xy_idx(tile.x, tile.y);
+                if map.tiles[idx] == TileType::Wall {
+                    break;
+                }
+                landing = *tile;
+                victim = map.tile_content[idx]
+                    .iter()
+                    .find(|mob| **mob != entity && combat_stats.get(**mob).is_some())
+                    .copied();
+                if victim.is_some() {
+                    break;
+                }
+            }
+
+            backpack.remove(to_throw.item);
+            positions
+                .insert(
+                    to_throw.item,
+                    Position {
+                        x: landing.x,
+                        y: landing.y,
+                    },
+                )
+                .expect("Unable to insert position");
+            noise.make_noise(landing.x, landing.y, CLATTER_VOLUME);
+
+            if let Some(victim) = victim {
+                let (damage, damage_type) = match throw_damage.get(to_throw.item) {
+                    Some(throw) => (rng.roll(throw.damage), throw.damage_type),
+                    // Anything else hurts more the heavier it is
+                    None => {
+                        let pounds = weights.get(to_throw.item).map_or(0.0, |w| w.pounds);
+                        (1 + (pounds / 3.0) as i32, DamageType::Blunt)
+                    }
+                };
+                let damage = i32::max(1, damage + attr_bonus(thrower_attributes.strength));
+                gamelog.entries.push(format!(
+                    "{} throws the {} at {}, hitting for {} hp.",
+                    names.get(entity).unwrap().name,
+                    item_name,
+                    names.get(victim).unwrap().name,
+                    damage
+                ));
+                SufferDamage::new_damage(
+                    &mut suffer_damage,
+                    victim,
+                    damage,
+                    damage_type,
+                    entity,
+                    Some(to_throw.item),
+                );
+            } else if is_player {
+                gamelog
+                    .entries
+                    .push(format!("You throw the {}.", item_name));
+            }
+
+            // Potions smash and splash whatever is in them over everyone nearby
+            if fragile.get(to_throw.item).is_some() {
+                if map.visible_tiles[map.xy_idx(landing.x, landing.y)] {
+                    gamelog.entries.push(format!("The {} shatters!", item_name));
+                }
+                if aoe.get(to_throw.item).is_none() {
+                    aoe.insert(to_throw.item, AreaOfEffect { radius: 1 })
+                        .expect("Unable to insert area of effect");
+                }
+                wants_use
+                    .insert(
+                        entity,
+                        WantsToUseItem {
+                            item: to_throw.item,
+                            target: Some(landing),
+                        },
+                    )
+                    .expect("Unable to insert intent");
+            }
+        }
+
+        wants_throw.clear();
+    }
+}
+
 pub struct ItemDropSystem {}
 
 impl<'a> System<'a> for ItemDropSystem {
@@ -306,16 +904,38 @@ pub struct ItemRemoveSystem {}
 impl<'a> System<'a> for ItemRemoveSystem {
     #[allow(clippy::type_complexity)]
     type SystemData = (
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
         Entities<'a>,
         WriteStorage<'a, WantsToRemoveItem>,
         WriteStorage<'a, Equipped>,
         WriteStorage<'a, InBackpack>,
+        ReadStorage<'a, Cursed>,
+        ReadStorage<'a, Name>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (entities, mut wants_remove, mut equipped, mut backpack) = data;
+        let (
+            player_entity,
+            mut gamelog,
+            entities,
+            mut wants_remove,
+            mut equipped,
+            mut backpack,
+            cursed,
+            names,
+        ) = data;
 
         for (entity, to_remove) in (&entities, &wants_remove).join() {
+            if cursed.get(to_remove.item).is_some() {
+                if entity == *player_entity {
+                    gamelog.entries.push(format!(
+                        "You can't take off the {}!",
+                        names.get(to_remove.item).unwrap().name
+                    ));
+                }
+                continue;
+            }
             equipped.remove(to_remove.item);
             backpack
                 .insert(to_remove.item, InBackpack { owner: entity })
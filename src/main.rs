@@ -6,6 +6,10 @@ use specs::saveload::{SimpleMarker, SimpleMarkerAllocator};
 
 mod components;
 pub use components::*;
+mod cookbook;
+use cookbook::Cookbook;
+mod gameclock;
+pub use gameclock::GameClock;
 mod gamelog;
 pub use gamelog::GameLog;
 mod gui;
@@ -13,20 +17,40 @@ mod map;
 pub use map::*;
 mod damage_system;
 use damage_system::DamageSystem;
+mod encumbrance_system;
+use encumbrance_system::EncumbranceSystem;
+mod hunger_system;
+mod identification;
+use identification::Identification;
 mod inventory_system;
+use hunger_system::HungerSystem;
 mod map_indexing_system;
 use map_indexing_system::MapIndexingSystem;
 mod melee_combat_system;
 use melee_combat_system::MeleeCombatSystem;
+mod detection_system;
+use detection_system::DetectionSystem;
+mod ranged_combat_system;
+use ranged_combat_system::RangedCombatSystem;
 mod monster_ai_system;
 use monster_ai_system::MonsterAI;
+mod pet_ai_system;
+use pet_ai_system::PetAI;
+mod noise_system;
+use noise_system::{NoiseEvents, NoiseSystem};
 mod player;
 pub use player::*;
 mod random_table;
 mod rect;
 pub use rect::Rect;
+mod regeneration_system;
 mod saveload_system;
+mod shop;
+use shop::PriceList;
 mod spawner;
+use regeneration_system::RegenerationSystem;
+mod status_effect_system;
+use status_effect_system::StatusEffectSystem;
 mod visibility_system;
 use visibility_system::VisibilitySystem;
 
@@ -42,13 +66,29 @@ pub enum RunState {
         range: i32,
         item: Entity,
     },
+    ShowFireTarget {
+        range: i32,
+    },
+    ShowThrowItem,
+    ShowThrowTarget {
+        item: Entity,
+    },
     MainMenu {
         menu_selection: gui::MainMenuSelection,
     },
     SaveGame,
     NextLevel,
-    ShowRemoveItem,
+    ShowEquipment,
+    ShowCookMenu,
+    ShowIdentify {
+        scroll: Entity,
+    },
+    ShowShop {
+        vendor: Entity,
+        selling: bool,
+    },
     GameOver,
+    LevelUp,
 }
 
 pub struct State {
@@ -59,22 +99,40 @@ impl State {
     fn run_systems(&mut self) {
         let mut vis = VisibilitySystem {};
         vis.run_now(&self.ecs);
+        let mut noise = NoiseSystem {};
+        noise.run_now(&self.ecs);
+        let mut detection = DetectionSystem {};
+        detection.run_now(&self.ecs);
         let mut mob = MonsterAI {};
         mob.run_now(&self.ecs);
+        let mut pets = PetAI {};
+        pets.run_now(&self.ecs);
+        let mut statuses = StatusEffectSystem {};
+        statuses.run_now(&self.ecs);
+        let mut regeneration = RegenerationSystem {};
+        regeneration.run_now(&self.ecs);
+        let mut hunger = HungerSystem {};
+        hunger.run_now(&self.ecs);
         let mut mapindex = MapIndexingSystem {};
         mapindex.run_now(&self.ecs);
         let mut melee = MeleeCombatSystem {};
         melee.run_now(&self.ecs);
+        let mut ranged = RangedCombatSystem {};
+        ranged.run_now(&self.ecs);
         let mut damage = DamageSystem {};
         damage.run_now(&self.ecs);
         let mut item_collection = inventory_system::ItemCollectionSystem {};
         item_collection.run_now(&self.ecs);
+        let mut item_throw = inventory_system::ItemThrowSystem {};
+        item_throw.run_now(&self.ecs);
         let mut itemuse = inventory_system::ItemUseSystem {};
         itemuse.run_now(&self.ecs);
         let mut drop_items = inventory_system::ItemDropSystem {};
         drop_items.run_now(&self.ecs);
         let mut item_remove = inventory_system::ItemRemoveSystem {};
         item_remove.run_now(&self.ecs);
+        let mut encumbrance = EncumbranceSystem {};
+        encumbrance.run_now(&self.ecs);
         self.ecs.maintain();
     }
 
@@ -83,29 +141,45 @@ impl State {
         let player = self.ecs.read_storage::<Player>();
         let backpack = self.ecs.read_storage::<InBackpack>();
         let equipped = self.ecs.read_storage::<Equipped>();
+        let pets = self.ecs.read_storage::<Pet>();
+        let positions = self.ecs.read_storage::<Position>();
         let player_entity = self.ecs.fetch::<Entity>();
+        let player_pos = self.ecs.fetch::<Point>();
+
+        // Pets only come along if they're right next to the player
+        let mut travellers: Vec<Entity> = vec![*player_entity];
+        for (entity, _pet, pos) in (&entities, &pets, &positions).join() {
+            let distance =
+                rltk::DistanceAlg::Pythagoras.distance2d(Point::new(pos.x, pos.y), *player_pos);
+            if distance < 1.5 {
+                travellers.push(entity);
+            }
+        }
 
         let mut to_delete: Vec<Entity> = Vec::new();
         for entity in entities.join() {
             let mut should_delete = true;
 
-            // Don't delete the player
+            // Don't delete the player or their pet
             let p = player.get(entity);
             if let Some(_p) = p {
                 should_delete = false;
             }
+            if travellers.contains(&entity) {
+                should_delete = false;
+            }
 
-            // Don't delete the player's equipment
+            // Don't delete the travellers' equipment
             let bp = backpack.get(entity);
             if let Some(bp) = bp {
-                if bp.owner == *player_entity {
+                if travellers.contains(&bp.owner) {
                     should_delete = false;
                 }
             }
 
             let eq = equipped.get(entity);
             if let Some(eq) = eq {
-                if eq.owner == *player_entity {
+                if travellers.contains(&eq.owner) {
                     should_delete = false;
                 }
             }
@@ -139,6 +213,20 @@ impl State {
             spawner::spawn_room(&mut self.ecs, room, worldmap.depth);
         }
 
+        // Now and then somebody has set up shop
+        let vendor_room = {
+            let mut rng = self.ecs.write_resource::<rltk::RandomNumberGenerator>();
+            // Never in the player's starting room
+            if worldmap.rooms.len() > 1 && rng.roll_dice(1, 3) == 1 {
+                Some(rng.range(1, worldmap.rooms.len()))
+            } else {
+                None
+            }
+        };
+        if let Some(room) = vendor_room {
+            spawner::vendor(&mut self.ecs, &worldmap.rooms[room], worldmap.depth);
+        }
+
         // Place the player and update resources
         let (player_x, player_y) = worldmap.rooms[0].center();
         let mut player_position = self.ecs.write_resource::<Point>();
@@ -158,6 +246,25 @@ impl State {
             vs.dirty = true;
         }
 
+        // Any pet that came along arrives at the player's side, leaving its grudges
+        // behind with the old level
+        let entities = self.ecs.entities();
+        let pets = self.ecs.read_storage::<Pet>();
+        let mut grudges = self.ecs.write_storage::<Grudge>();
+        for (pet, _pet, pos, vs) in (
+            &entities,
+            &pets,
+            &mut position_components,
+            &mut viewshed_components,
+        )
+            .join()
+        {
+            pos.x = player_x + 1;
+            pos.y = player_y;
+            vs.dirty = true;
+            grudges.remove(pet);
+        }
+
         // Notify the player and give them some health
         let mut gamelog = self.ecs.fetch_mut::<gamelog::GameLog>();
         gamelog
@@ -170,6 +277,122 @@ impl State {
         }
     }
 
+    fn player_loses_turn(&self) -> bool {
+        let player_entity = self.ecs.fetch::<Entity>();
+        let statuses = self.ecs.read_storage::<StatusEffects>();
+        statuses.get(*player_entity).is_some_and(|s| s.loses_turn())
+    }
+
+    fn player_has_free_move(&self) -> bool {
+        let player_entity = self.ecs.fetch::<Entity>();
+        let statuses = self.ecs.read_storage::<StatusEffects>();
+        statuses
+            .get(*player_entity)
+            .is_some_and(|s| s.turns_left(StatusEffect::Hasted) % 2 == 1)
+    }
+
+    fn player_can_level_up(&self) -> bool {
+        let player_entity = self.ecs.fetch::<Entity>();
+        let experience = self.ecs.read_storage::<Experience>();
+        experience
+            .get(*player_entity)
+            .is_some_and(|exp| exp.xp >= exp.next_level_xp())
+    }
+
+    fn level_up_player(&mut self, choice: gui::LevelUpChoice) {
+        let player_entity = self.ecs.fetch::<Entity>();
+        let mut experience = self.ecs.write_storage::<Experience>();
+        let mut combat_stats = self.ecs.write_storage::<CombatStats>();
+        let mut gamelog = self.ecs.fetch_mut::<gamelog::GameLog>();
+
+        if let Some(exp) = experience.get_mut(*player_entity) {
+            exp.level += 1;
+            gamelog
+                .entries
+                .push(format!("Welcome to level {}!", exp.level));
+        }
+        if let Some(stats) = combat_stats.get_mut(*player_entity) {
+            match choice {
+                gui::LevelUpChoice::Health => {
+                    stats.max_hp += 10;
+                    stats.hp += 10;
+                }
+                gui::LevelUpChoice::Power => stats.power += 1,
+                gui::LevelUpChoice::Defense => stats.defense += 1,
+            }
+        }
+    }
+
+    /// Reads an identify scroll on one of the player's unidentified items or
+    /// pieces of gear. The scroll identifies itself in the process.
+    fn identify_with_scroll(&mut self, scroll: Entity, item: Entity) {
+        {
+            let entities = self.ecs.entities();
+            let mut names = self.ecs.write_storage::<Name>();
+            let mut unidentified = self.ecs.write_storage::<Unidentified>();
+            let mut identification = self.ecs.write_resource::<Identification>();
+            let mut gamelog = self.ecs.fetch_mut::<gamelog::GameLog>();
+
+            for target in [scroll, item].iter() {
+                let real_name = unidentified
+                    .get(*target)
+                    .map(|unknown| unknown.real_name.clone());
+                if let Some(real_name) = real_name {
+                    gamelog.entries.push(format!(
+                        "You identify the {} as {}.",
+                        names.get(*target).unwrap().name,
+                        real_name
+                    ));
+                    identification::identify(
+                        &mut identification,
+                        &entities,
+                        &mut names,
+                        &mut unidentified,
+                        &real_name,
+                    );
+                }
+            }
+
+            // Gear gives away whether it's cursed
+            let equippable = self.ecs.read_storage::<Equippable>();
+            let mut cursed = self.ecs.write_storage::<Cursed>();
+            let mut known_clean = self.ecs.write_storage::<KnownClean>();
+            if equippable.get(item).is_some() {
+                let name = names.get_mut(item).unwrap();
+                match cursed.get_mut(item) {
+                    Some(curse) => {
+                        gamelog
+                            .entries
+                            .push(format!("The {} is cursed!", name.name));
+                        inventory_system::reveal_curse(curse, name);
+                    }
+                    None => {
+                        gamelog
+                            .entries
+                            .push(format!("The {} is not cursed.", name.name));
+                        known_clean
+                            .insert(item, KnownClean {})
+                            .expect("Unable to insert clean mark");
+                    }
+                }
+            }
+        }
+
+        // Only one scroll off the stack gets used up
+        let last_one = match self.ecs.write_storage::<Stackable>().get_mut(scroll) {
+            Some(stack) if stack.quantity > 1 => {
+                stack.quantity -= 1;
+                false
+            }
+            _ => true,
+        };
+        if last_one {
+            self.ecs
+                .delete_entity(scroll)
+                .expect("Unable to delete scroll");
+        }
+    }
+
     fn game_over_cleanup(&mut self) {
         // Delete everything
         let mut to_delete = Vec::new();
@@ -180,6 +403,15 @@ impl State {
             self.ecs.delete_entity(*del).expect("Deletion failed");
         }
 
+        *self.ecs.write_resource::<GameClock>() = GameClock::default();
+
+        // A new run gets a new set of unidentified item names
+        let seed = self
+            .ecs
+            .write_resource::<rltk::RandomNumberGenerator>()
+            .next_u64();
+        *self.ecs.write_resource::<Identification>() = Identification::new(seed);
+
         // Build a new map and place the player
         let worldmap;
         {
@@ -196,6 +428,7 @@ impl State {
         // Place the player and update resources
         let (player_x, player_y) = worldmap.rooms[0].center();
         let player_entity = spawner::player(&mut self.ecs, player_x, player_y);
+        spawner::pet(&mut self.ecs, player_x + 1, player_y);
         let mut player_position = self.ecs.write_resource::<Point>();
         *player_position = Point::new(player_x, player_y);
         let mut position_components = self.ecs.write_storage::<Position>();
@@ -260,17 +493,33 @@ impl GameState for State {
                 newrunstate = RunState::AwaitingInput;
             }
             RunState::AwaitingInput => {
-                newrunstate = player_input(self, ctx);
+                if self.player_loses_turn() {
+                    let mut gamelog = self.ecs.fetch_mut::<gamelog::GameLog>();
+                    gamelog
+                        .entries
+                        .push("You can't get your paws to move!".to_string());
+                    newrunstate = RunState::PlayerTurn;
+                } else {
+                    newrunstate = player_input(self, ctx);
+                }
             }
             RunState::PlayerTurn => {
                 self.run_systems();
                 self.ecs.maintain();
                 newrunstate = RunState::MonsterTurn;
+                // Haste gives the player a free move every other turn
+                if self.player_has_free_move() {
+                    newrunstate = RunState::AwaitingInput;
+                }
             }
             RunState::MonsterTurn => {
                 self.run_systems();
                 self.ecs.maintain();
+                self.ecs.write_resource::<GameClock>().turn += 1;
                 newrunstate = RunState::AwaitingInput;
+                if self.player_can_level_up() {
+                    newrunstate = RunState::LevelUp;
+                }
             }
             RunState::ShowInventory => {
                 let result = gui::show_inventory(self, ctx);
@@ -281,7 +530,24 @@ impl GameState for State {
                         let item_entity = result.1.unwrap();
                         let is_ranged = self.ecs.read_storage::<Ranged>();
                         let is_item_ranged = is_ranged.get(item_entity);
-                        if let Some(is_item_ranged) = is_item_ranged {
+                        let identifies = self.ecs.read_storage::<IdentifiesItem>();
+                        let charges = self.ecs.read_storage::<Charges>();
+                        // A spent wand isn't worth aiming, or spending a turn on
+                        if charges.get(item_entity).is_some_and(|c| c.remaining < 1) {
+                            let names = self.ecs.read_storage::<Name>();
+                            self.ecs
+                                .write_resource::<gamelog::GameLog>()
+                                .entries
+                                .push(format!(
+                                    "The {} is out of charges.",
+                                    names.get(item_entity).unwrap().name
+                                ));
+                            newrunstate = RunState::AwaitingInput;
+                        } else if identifies.get(item_entity).is_some() {
+                            newrunstate = RunState::ShowIdentify {
+                                scroll: item_entity,
+                            };
+                        } else if let Some(is_item_ranged) = is_item_ranged {
                             newrunstate = RunState::ShowTargeting {
                                 range: is_item_ranged.range,
                                 item: item_entity,
@@ -308,7 +574,9 @@ impl GameState for State {
                     gui::ItemMenuResult::Cancel => newrunstate = RunState::AwaitingInput,
                     gui::ItemMenuResult::NoResponse => {}
                     gui::ItemMenuResult::Selected => {
-                        let item_entity = result.1.unwrap();
+                        // Stacks are dropped one at a time
+                        let item_entity =
+                            inventory_system::split_stack(&mut self.ecs, result.1.unwrap(), 1);
                         let mut intent = self.ecs.write_storage::<WantsToDropItem>();
                         intent
                             .insert(
@@ -340,6 +608,54 @@ impl GameState for State {
                     }
                 }
             }
+            RunState::ShowThrowItem => {
+                let result = gui::throw_item_menu(self, ctx);
+                match result.0 {
+                    gui::ItemMenuResult::Cancel => newrunstate = RunState::AwaitingInput,
+                    gui::ItemMenuResult::NoResponse => {}
+                    gui::ItemMenuResult::Selected => {
+                        newrunstate = RunState::ShowThrowTarget {
+                            item: result.1.unwrap(),
+                        };
+                    }
+                }
+            }
+            RunState::ShowThrowTarget { item } => {
+                let result = gui::ranged_target(self, ctx, inventory_system::THROW_RANGE);
+                match result.0 {
+                    gui::ItemMenuResult::Cancel => newrunstate = RunState::AwaitingInput,
+                    gui::ItemMenuResult::NoResponse => {}
+                    gui::ItemMenuResult::Selected => {
+                        if let Some(target) = result.1 {
+                            let item = inventory_system::split_stack(&mut self.ecs, item, 1);
+                            let mut intent = self.ecs.write_storage::<WantsToThrowItem>();
+                            intent
+                                .insert(
+                                    *self.ecs.fetch::<Entity>(),
+                                    WantsToThrowItem { item, target },
+                                )
+                                .expect("Unable to insert intent");
+                        }
+                        newrunstate = RunState::PlayerTurn;
+                    }
+                }
+            }
+            RunState::ShowFireTarget { range } => {
+                let result = gui::ranged_target(self, ctx, range);
+                match result.0 {
+                    gui::ItemMenuResult::Cancel => newrunstate = RunState::AwaitingInput,
+                    gui::ItemMenuResult::NoResponse => {}
+                    gui::ItemMenuResult::Selected => {
+                        if let Some(target) = result.1 {
+                            let mut intent = self.ecs.write_storage::<WantsToShoot>();
+                            intent
+                                .insert(*self.ecs.fetch::<Entity>(), WantsToShoot { target })
+                                .expect("Unable to insert intent");
+                        }
+                        newrunstate = RunState::PlayerTurn;
+                    }
+                }
+            }
             RunState::MainMenu { .. } => {
                 let result = gui::main_menu(self, ctx);
                 match result {
@@ -371,8 +687,8 @@ impl GameState for State {
                 self.goto_next_level();
                 newrunstate = RunState::PreRun;
             }
-            RunState::ShowRemoveItem => {
-                let result = gui::remove_item_menu(self, ctx);
+            RunState::ShowEquipment => {
+                let result = gui::equipment_menu(self, ctx);
                 match result.0 {
                     gui::ItemMenuResult::Cancel => newrunstate = RunState::AwaitingInput,
                     gui::ItemMenuResult::NoResponse => {}
@@ -389,8 +705,60 @@ impl GameState for State {
                     }
                 }
             }
+            RunState::ShowCookMenu => {
+                let result = gui::cook_menu(self, ctx);
+                match result.0 {
+                    gui::ItemMenuResult::Cancel => newrunstate = RunState::AwaitingInput,
+                    gui::ItemMenuResult::NoResponse => {}
+                    gui::ItemMenuResult::Selected => {
+                        newrunstate = RunState::AwaitingInput;
+                        if cookbook::cook(&mut self.ecs, result.1.unwrap()) {
+                            newrunstate = RunState::PlayerTurn;
+                        }
+                    }
+                }
+            }
+            RunState::ShowIdentify { scroll } => {
+                let result = gui::identify_item_menu(self, ctx, scroll);
+                match result.0 {
+                    gui::ItemMenuResult::Cancel => newrunstate = RunState::AwaitingInput,
+                    gui::ItemMenuResult::NoResponse => {}
+                    gui::ItemMenuResult::Selected => {
+                        self.identify_with_scroll(scroll, result.1.unwrap());
+                        newrunstate = RunState::PlayerTurn;
+                    }
+                }
+            }
+            RunState::ShowShop { vendor, selling } => {
+                let result = gui::shop_menu(self, ctx, vendor, selling);
+                match result.0 {
+                    gui::ShopMenuResult::Cancel => newrunstate = RunState::AwaitingInput,
+                    gui::ShopMenuResult::NoResponse => {}
+                    gui::ShopMenuResult::SwitchMode => {
+                        newrunstate = RunState::ShowShop {
+                            vendor,
+                            selling: !selling,
+                        }
+                    }
+                    gui::ShopMenuResult::Selected => {
+                        if selling {
+                            shop::sell(&mut self.ecs, vendor, result.1.unwrap());
+                        } else {
+                            shop::buy(&mut self.ecs, vendor, result.1.unwrap());
+                        }
+                    }
+                }
+            }
+            RunState::LevelUp => {
+                if let Some(choice) = gui::level_up_menu(ctx) {
+                    self.level_up_player(choice);
+                    if !self.player_can_level_up() {
+                        newrunstate = RunState::AwaitingInput;
+                    }
+                }
+            }
             RunState::GameOver => {
-                let result = gui::game_over(ctx);
+                let result = gui::game_over(&self.ecs, ctx);
                 match result {
                     gui::GameOverResult::NoSelection => {}
                     gui::GameOverResult::QuitToMenu => {
@@ -432,43 +800,94 @@ fn main() -> rltk::BError {
         .build()?;
     // context.with_post_scanlines(true);
     let mut gs = State { ecs: World::new() };
+    gs.ecs.register::<Ammunition>();
     gs.ecs.register::<AreaOfEffect>();
+    gs.ecs.register::<Asleep>();
+    gs.ecs.register::<Attributes>();
     gs.ecs.register::<BlocksTile>();
+    gs.ecs.register::<Charges>();
     gs.ecs.register::<CombatStats>();
-    gs.ecs.register::<Confusion>();
     gs.ecs.register::<Consumable>();
+    gs.ecs.register::<Cursed>();
     gs.ecs.register::<DefenseBonus>();
+    gs.ecs.register::<Encumbrance>();
     gs.ecs.register::<Equipped>();
     gs.ecs.register::<Equippable>();
+    gs.ecs.register::<Experience>();
+    gs.ecs.register::<Fragile>();
+    gs.ecs.register::<Gold>();
+    gs.ecs.register::<GroupMember>();
+    gs.ecs.register::<Grudge>();
+    gs.ecs.register::<HungerClock>();
+    gs.ecs.register::<IdentifiesItem>();
     gs.ecs.register::<InBackpack>();
     gs.ecs.register::<InflictsDamage>();
+    gs.ecs.register::<InflictsStatus>();
+    gs.ecs.register::<Investigating>();
+    gs.ecs.register::<Ingredient>();
     gs.ecs.register::<Item>();
+    gs.ecs.register::<KilledBy>();
+    gs.ecs.register::<KnownClean>();
+    gs.ecs.register::<LootTable>();
     gs.ecs.register::<MeleePowerBonus>();
+    gs.ecs.register::<MeleeWeapon>();
     gs.ecs.register::<Monster>();
     gs.ecs.register::<Name>();
+    gs.ecs.register::<NaturalAttack>();
+    gs.ecs.register::<Noticed>();
+    gs.ecs.register::<Pet>();
     gs.ecs.register::<Player>();
     gs.ecs.register::<Position>();
+    gs.ecs.register::<ProvidesFood>();
     gs.ecs.register::<ProvidesHealing>();
     gs.ecs.register::<Ranged>();
+    gs.ecs.register::<Purse>();
+    gs.ecs.register::<RangedAttacker>();
+    gs.ecs.register::<RangedWeapon>();
+    gs.ecs.register::<RemovesCurse>();
     gs.ecs.register::<Renderable>();
+    gs.ecs.register::<Resistant>();
     gs.ecs.register::<SerializationHelper>();
     gs.ecs.register::<SimpleMarker<SerializeMe>>();
+    gs.ecs.register::<Skills>();
+    gs.ecs.register::<StatusEffects>();
+    gs.ecs.register::<Stackable>();
+    gs.ecs.register::<Stealth>();
+    gs.ecs.register::<Stove>();
     gs.ecs.register::<SufferDamage>();
+    gs.ecs.register::<ThrowDamage>();
+    gs.ecs.register::<TwoHanded>();
+    gs.ecs.register::<Unidentified>();
+    gs.ecs.register::<Value>();
+    gs.ecs.register::<Vendor>();
     gs.ecs.register::<Viewshed>();
+    gs.ecs.register::<Vulnerable>();
+    gs.ecs.register::<WeaponAffix>();
+    gs.ecs.register::<Weight>();
     gs.ecs.register::<WantsToMelee>();
     gs.ecs.register::<WantsToPickupItem>();
     gs.ecs.register::<WantsToDropItem>();
     gs.ecs.register::<WantsToUseItem>();
     gs.ecs.register::<WantsToRemoveItem>();
+    gs.ecs.register::<WantsToShoot>();
+    gs.ecs.register::<WantsToThrowItem>();
 
     gs.ecs.insert(SimpleMarkerAllocator::<SerializeMe>::new());
 
     let map = Map::new_map_rooms_and_corridors(1);
     gs.ecs.insert(rltk::RandomNumberGenerator::new());
+    gs.ecs.insert(Cookbook::load());
+    gs.ecs.insert(PriceList::load());
+    let seed = gs
+        .ecs
+        .write_resource::<rltk::RandomNumberGenerator>()
+        .next_u64();
+    gs.ecs.insert(Identification::new(seed));
 
     let (player_x, player_y) = map.rooms[0].center();
     // Add player
     let player_entity = spawner::player(&mut gs.ecs, player_x, player_y);
+    spawner::pet(&mut gs.ecs, player_x + 1, player_y);
 
     for room in map.rooms.iter().skip(1) {
         spawner::spawn_room(&mut gs.ecs, room, map.depth);
@@ -479,6 +898,8 @@ fn main() -> rltk::BError {
     gs.ecs.insert(RunState::MainMenu {
         menu_selection: gui::MainMenuSelection::NewGame,
     });
+    gs.ecs.insert(NoiseEvents::default());
+    gs.ecs.insert(GameClock::default());
     gs.ecs.insert(gamelog::GameLog {
         entries: vec!["Welcome to doghack!".to_string()],
     });
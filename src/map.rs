@@ -125,6 +125,15 @@ impl Map {
         }
     }
 
+    /// Returns true if nothing blocks a straight shot between two points. The end
+    /// points themselves aren't checked, since the shooter and target occupy them.
+    pub fn line_of_fire(&self, start: Point, end: Point) -> bool {
+        rltk::line2d(rltk::LineAlg::Bresenham, start, end)
+            .iter()
+            .filter(|p| **p != start && **p != end)
+            .all(|p| !self.blocked[self.xy_idx(p.x, p.y)])
+    }
+
     pub fn clear_content_index(&mut self) {
         for content in self.tile_content.iter_mut() {
             content.clear();
@@ -1,20 +1,41 @@
-use super::{gamelog::GameLog, CombatStats, Equipped, Name, SufferDamage, WantsToMelee};
+use super::{
+    attr_bonus,
+    gamelog::GameLog,
+    noise_system::{NoiseEvents, COMBAT_VOLUME},
+    Affix, Attributes, CombatStats, DamageType, Equipped, MeleeWeapon, Name, NaturalAttack, Player,
+    Position, Skills, StatusEffect, StatusEffects, SufferDamage, WantsToMelee, WeaponAffix,
+};
 use crate::{DefenseBonus, MeleePowerBonus};
+use rltk::{DiceType, RandomNumberGenerator};
 use specs::prelude::*;
 
+/// Armour class is this plus the defender's defense, armour, agility and defense skill.
+const BASE_ARMOUR_CLASS: i32 = 10;
+
 pub struct MeleeCombatSystem {}
 
 impl<'a> System<'a> for MeleeCombatSystem {
+    #[allow(clippy::type_complexity)]
     type SystemData = (
         Entities<'a>,
         WriteStorage<'a, WantsToMelee>,
         ReadStorage<'a, Name>,
-        ReadStorage<'a, CombatStats>,
+        WriteStorage<'a, CombatStats>,
         WriteStorage<'a, SufferDamage>,
         WriteExpect<'a, GameLog>,
         ReadStorage<'a, MeleePowerBonus>,
         ReadStorage<'a, DefenseBonus>,
         ReadStorage<'a, Equipped>,
+        ReadStorage<'a, Position>,
+        WriteExpect<'a, NoiseEvents>,
+        WriteExpect<'a, RandomNumberGenerator>,
+        ReadStorage<'a, MeleeWeapon>,
+        ReadStorage<'a, NaturalAttack>,
+        ReadStorage<'a, Attributes>,
+        WriteStorage<'a, Skills>,
+        ReadStorage<'a, Player>,
+        WriteStorage<'a, StatusEffects>,
+        ReadStorage<'a, WeaponAffix>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -22,18 +43,39 @@ impl<'a> System<'a> for MeleeCombatSystem {
             entities,
             mut wants_melee,
             names,
-            combat_stats,
+            mut combat_stats,
             mut inflict_damage,
             mut log,
             melee_power_bonuses,
             defense_bonuses,
             equipped,
+            positions,
+            mut noise,
+            mut rng,
+            melee_weapons,
+            natural_attacks,
+            attributes,
+            mut skills,
+            players,
+            mut statuses,
+            weapon_affixes,
         ) = data;
 
+        let default_attributes = Attributes::default();
+        let mut vampiric_heals: Vec<(Entity, i32)> = Vec::new();
+
         for (entity, wants_melee, name, stats) in
             (&entities, &wants_melee, &names, &combat_stats).join()
         {
             if stats.hp > 0 {
+                if let Some(pos) = positions.get(entity) {
+                    noise.make_noise(pos.x, pos.y, COMBAT_VOLUME);
+                }
+
+                let attacker_attributes = attributes.get(entity).unwrap_or(&default_attributes);
+                let strength_bonus = attr_bonus(attacker_attributes.strength);
+                let melee_skill = skills.get(entity).map_or(0, |s| s.melee.level);
+
                 let mut offensive_bonus = 0;
                 for (_item_entity, power_bonus, equipped_by) in
                     (&entities, &melee_power_bonuses, &equipped).join()
@@ -43,6 +85,23 @@ impl<'a> System<'a> for MeleeCombatSystem {
                     }
                 }
 
+                // Hit with whatever we're wielding, falling back on teeth and fists
+                let (mut damage_dice, mut damage_type) = natural_attacks
+                    .get(entity)
+                    .map_or((DiceType::new(1, 4, 0), DamageType::Blunt), |natural| {
+                        (natural.damage, natural.damage_type)
+                    });
+                let mut weapon_used: Option<Entity> = None;
+                for (weapon_entity, weapon, equipped_by) in
+                    (&entities, &melee_weapons, &equipped).join()
+                {
+                    if equipped_by.owner == entity {
+                        damage_dice = weapon.damage;
+                        damage_type = weapon.damage_type;
+                        weapon_used = Some(weapon_entity);
+                    }
+                }
+
                 let target_stats = combat_stats.get(wants_melee.target).unwrap();
                 if target_stats.hp > 0 {
                     let target_name = names.get(wants_melee.target).unwrap();
@@ -56,26 +115,165 @@ impl<'a> System<'a> for MeleeCombatSystem {
                         }
                     }
 
-                    let damage = i32::max(
-                        0,
-                        (stats.power + offensive_bonus) - (target_stats.defense + defensive_bonus),
-                    );
-                    if damage == 0 {
+                    let target_attributes = attributes
+                        .get(wants_melee.target)
+                        .unwrap_or(&default_attributes);
+                    let defense_skill = skills
+                        .get(wants_melee.target)
+                        .map_or(0, |s| s.defense.level);
+
+                    let armour_class = BASE_ARMOUR_CLASS
+                        + target_stats.defense
+                        + defensive_bonus
+                        + attr_bonus(target_attributes.agility)
+                        + defense_skill;
+                    let mut damage_dealt = 0;
+                    let natural_roll = rng.roll_dice(1, 20);
+                    let attack_roll = natural_roll + offensive_bonus + strength_bonus + melee_skill;
+
+                    if natural_roll == 1 {
                         log.entries.push(format!(
-                            "{} is unable to hurt {}",
+                            "{} fumbles an attack on {} (natural 1).",
                             &name.name, &target_name.name
                         ));
-                    } else {
+                    } else if natural_roll == 20 {
+                        // Criticals roll the weapon's dice twice
+                        let damage = i32::max(
+                            1,
+                            rng.roll(damage_dice)
+                                + rng.roll(damage_dice)
+                                + stats.power
+                                + offensive_bonus
+                                + strength_bonus,
+                        );
+                        damage_dealt = damage;
                         log.entries.push(format!(
-                            "{} hits {}, for {} hp.",
+                            "{} critically hits {}, for {} hp! (natural 20)",
                             &name.name, &target_name.name, damage
                         ));
-                        SufferDamage::new_damage(&mut inflict_damage, wants_melee.target, damage)
+                        SufferDamage::new_damage(
+                            &mut inflict_damage,
+                            wants_melee.target,
+                            damage,
+                            damage_type,
+                            entity,
+                            weapon_used,
+                        );
+                    } else if attack_roll >= armour_class {
+                        let damage = i32::max(
+                            1,
+                            rng.roll(damage_dice) + stats.power + offensive_bonus + strength_bonus,
+                        );
+                        damage_dealt = damage;
+                        log.entries.push(format!(
+                            "{} hits {}, for {} hp. ({} vs AC {})",
+                            &name.name, &target_name.name, damage, attack_roll, armour_class
+                        ));
+                        SufferDamage::new_damage(
+                            &mut inflict_damage,
+                            wants_melee.target,
+                            damage,
+                            damage_type,
+                            entity,
+                            weapon_used,
+                        )
+                    } else {
+                        log.entries.push(format!(
+                            "{} misses {}. ({} vs AC {})",
+                            &name.name, &target_name.name, attack_roll, armour_class
+                        ));
+                        // Turning a blow aside is good practice
+                        if let Some(target_skills) = skills.get_mut(wants_melee.target) {
+                            if target_skills.defense.practise()
+                                && players.get(wants_melee.target).is_some()
+                            {
+                                log.entries.push(format!(
+                                    "Your defense skill improves to {}.",
+                                    target_skills.defense.level
+                                ));
+                            }
+                        }
+                    }
+
+                    // Affixed weapons do a little extra when they connect
+                    let affix = weapon_used
+                        .and_then(|w| weapon_affixes.get(w))
+                        .map(|a| a.affix);
+                    if damage_dealt > 0 {
+                        match affix {
+                            Some(Affix::Fire) => {
+                                add_status(
+                                    &mut statuses,
+                                    wants_melee.target,
+                                    StatusEffect::Burning,
+                                    2,
+                                    entity,
+                                    weapon_used,
+                                );
+                                log.entries
+                                    .push(format!("{} catches fire!", &target_name.name));
+                            }
+                            Some(Affix::Speed) => {
+                                add_status(
+                                    &mut statuses,
+                                    entity,
+                                    StatusEffect::Hasted,
+                                    2,
+                                    entity,
+                                    weapon_used,
+                                );
+                                log.entries.push(format!("{} is quickened!", &name.name));
+                            }
+                            Some(Affix::Vampiric) => {
+                                let drained = i32::max(1, damage_dealt / 2);
+                                vampiric_heals.push((entity, drained));
+                                log.entries
+                                    .push(format!("{} drinks in {} hp.", &name.name, drained));
+                            }
+                            None => {}
+                        }
+                    }
+
+                    // Any swing that isn't a fumble teaches the attacker something
+                    if natural_roll != 1 {
+                        if let Some(attacker_skills) = skills.get_mut(entity) {
+                            if attacker_skills.melee.practise() && players.get(entity).is_some() {
+                                log.entries.push(format!(
+                                    "Your melee skill improves to {}.",
+                                    attacker_skills.melee.level
+                                ));
+                            }
+                        }
                     }
                 }
             }
         }
 
+        for (entity, heal) in vampiric_heals {
+            if let Some(stats) = combat_stats.get_mut(entity) {
+                stats.hp = i32::min(stats.max_hp, stats.hp + heal);
+            }
+        }
+
         wants_melee.clear()
     }
 }
+
+fn add_status(
+    statuses: &mut WriteStorage<StatusEffects>,
+    target: Entity,
+    effect: StatusEffect,
+    turns: i32,
+    source: Entity,
+    weapon: Option<Entity>,
+) {
+    if let Some(effects) = statuses.get_mut(target) {
+        effects.add(effect, turns, Some(source), weapon);
+    } else {
+        let mut effects = StatusEffects::default();
+        effects.add(effect, turns, Some(source), weapon);
+        statuses
+            .insert(target, effects)
+            .expect("Unable to insert status");
+    }
+}
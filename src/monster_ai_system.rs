@@ -1,6 +1,12 @@
-use super::{Confusion, Map, Monster, Position, RunState, Viewshed, WantsToMelee};
+use super::{
+    Asleep, CombatStats, DefenseBonus, Equippable, Equipped, GroupMember, Grudge, InBackpack,
+    Investigating, Item, Map, MeleePowerBonus, Monster, Noticed, Position, ProvidesHealing,
+    RangedAttacker, RunState, StatusEffect, StatusEffects, Viewshed, WantsToMelee,
+    WantsToPickupItem, WantsToUseItem,
+};
 use rltk::Point;
 use specs::prelude::*;
+use std::collections::{HashMap, HashSet};
 
 pub struct MonsterAI {}
 
@@ -16,7 +22,23 @@ impl<'a> System<'a> for MonsterAI {
         ReadStorage<'a, Monster>,
         WriteStorage<'a, Position>,
         WriteStorage<'a, WantsToMelee>,
-        WriteStorage<'a, Confusion>,
+        ReadStorage<'a, StatusEffects>,
+        ReadStorage<'a, RangedAttacker>,
+        WriteStorage<'a, WantsToUseItem>,
+        WriteStorage<'a, WantsToPickupItem>,
+        ReadStorage<'a, CombatStats>,
+        ReadStorage<'a, Item>,
+        ReadStorage<'a, InBackpack>,
+        ReadStorage<'a, Equippable>,
+        ReadStorage<'a, Equipped>,
+        ReadStorage<'a, MeleePowerBonus>,
+        ReadStorage<'a, DefenseBonus>,
+        ReadStorage<'a, ProvidesHealing>,
+        ReadStorage<'a, GroupMember>,
+        WriteStorage<'a, Asleep>,
+        ReadStorage<'a, Noticed>,
+        WriteStorage<'a, Investigating>,
+        ReadStorage<'a, Grudge>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -30,30 +52,150 @@ impl<'a> System<'a> for MonsterAI {
             monster,
             mut position,
             mut wants_to_melee,
-            mut confusion,
+            statuses,
+            ranged_attackers,
+            mut wants_to_use,
+            mut wants_pickup,
+            combat_stats,
+            items,
+            backpack,
+            equippable,
+            equipped,
+            melee_power_bonuses,
+            defense_bonuses,
+            healing,
+            groups,
+            mut asleep,
+            noticed,
+            mut investigating,
+            grudges,
         ) = data;
 
         if *runstate != RunState::MonsterTurn {
             return;
         }
 
-        for (entity, mut viewshed, _monster, mut pos) in
+        // Items already spoken for this turn, so two monsters don't grab the same one
+        let mut claimed_items: Vec<Entity> = Vec::new();
+        let is_wanted = |item: Entity| {
+            items.get(item).is_some()
+                && (equippable.get(item).is_some() || healing.get(item).is_some())
+        };
+        let gear_score = |item: Entity| {
+            melee_power_bonuses.get(item).map_or(0, |b| b.power)
+                + defense_bonuses.get(item).map_or(0, |b| b.defense)
+        };
+
+        // Find each pack's leader, and which packs have a member hunting the player
+        let mut leader_positions: HashMap<Entity, Point> = HashMap::new();
+        let mut alerted_groups: HashSet<Entity> = HashSet::new();
+        for (entity, group, pos) in (&entities, &groups, &position).join() {
+            if group.leader == entity {
+                leader_positions.insert(entity, Point::new(pos.x, pos.y));
+            }
+            if noticed.get(entity).is_some() {
+                alerted_groups.insert(group.leader);
+            }
+        }
+
+        // Nobody sleeps through their pack going on the hunt
+        for (entity, group) in (&entities, &groups).join() {
+            if alerted_groups.contains(&group.leader) {
+                asleep.remove(entity);
+            }
+        }
+
+        // Where anyone holding a grudge can find its target
+        let grudge_targets: HashMap<Entity, (Entity, Point)> = (&entities, &grudges)
+            .join()
+            .filter_map(|(avenger, grudge)| {
+                position
+                    .get(grudge.against)
+                    .map(|pos| (avenger, (grudge.against, Point::new(pos.x, pos.y))))
+            })
+            .collect();
+
+        for (entity, viewshed, _monster, pos) in
             (&entities, &mut viewshed, &monster, &mut position).join()
         {
+            let group = groups.get(entity);
             let mut can_act = true;
 
-            let is_confused = confusion.get_mut(entity);
-            if let Some(i_am_confused) = is_confused {
-                i_am_confused.turns -= 1;
-                if i_am_confused.turns < 1 {
-                    confusion.remove(entity);
+            if let Some(status) = statuses.get(entity) {
+                if status.has(StatusEffect::Confused) || status.loses_turn() {
+                    can_act = false;
                 }
+            }
+            if asleep.get(entity).is_some() {
                 can_act = false;
             }
             if can_act {
-                if viewshed.visible_tiles.contains(&*player_pos) {
-                    let distance = rltk::DistanceAlg::Pythagoras
-                        .distance2d(Point::new(pos.x, pos.y), *player_pos);
+                let monster_pos = Point::new(pos.x, pos.y);
+                let carried: Vec<Entity> = (&entities, &backpack)
+                    .join()
+                    .filter(|(_item, pack)| pack.owner == entity)
+                    .map(|(item, _pack)| item)
+                    .collect();
+
+                // Eat something if we're badly hurt and have the means
+                if let Some(stats) = combat_stats.get(entity) {
+                    if stats.hp < stats.max_hp / 2 {
+                        if let Some(medicine) = carried.iter().find(|i| healing.get(**i).is_some())
+                        {
+                            wants_to_use
+                                .insert(
+                                    entity,
+                                    WantsToUseItem {
+                                        item: *medicine,
+                                        target: None,
+                                    },
+                                )
+                                .expect("Unable to insert intent");
+                            continue;
+                        }
+                    }
+                }
+
+                // Turn on anything that bit us, as long as it's still within reach
+                if let Some((target, target_pos)) = grudge_targets.get(&entity) {
+                    if rltk::DistanceAlg::Pythagoras.distance2d(monster_pos, *target_pos) < 1.5 {
+                        wants_to_melee
+                            .insert(entity, WantsToMelee { target: *target })
+                            .expect("Unable to insert attack");
+                        continue;
+                    }
+                }
+
+                let knows_where_player_is = noticed.get(entity).is_some()
+                    || group.is_some_and(|g| alerted_groups.contains(&g.leader));
+                if knows_where_player_is {
+                    let distance =
+                        rltk::DistanceAlg::Pythagoras.distance2d(monster_pos, *player_pos);
+
+                    if let Some(ranged) = ranged_attackers.get(entity) {
+                        // Back off if the player is too close, and shoot if we have a clear line
+                        if distance < ranged.min_range as f32 {
+                            if let Some(retreat) = step_away(&map, monster_pos, *player_pos) {
+                                move_monster(&mut map, pos, viewshed, retreat);
+                                continue;
+                            }
+                        }
+                        if distance <= ranged.max_range as f32
+                            && map.line_of_fire(monster_pos, *player_pos)
+                        {
+                            wants_to_use
+                                .insert(
+                                    entity,
+                                    WantsToUseItem {
+                                        item: ranged.ability,
+                                        target: Some(*player_pos),
+                                    },
+                                )
+                                .expect("Unable to insert intent");
+                            continue;
+                        }
+                    }
+
                     if distance < 1.5 {
                         wants_to_melee
                             .insert(
@@ -63,21 +205,121 @@ impl<'a> System<'a> for MonsterAI {
                                 },
                             )
                             .expect("Unable to insert attack");
-                    } else if viewshed.visible_tiles.contains(&*player_pos) {
-                        // Path to the player
+                    } else {
+                        // Path to the player. Pack members aim for an open tile around the
+                        // player instead, so they surround them rather than queue up.
+                        let mut goal = map.xy_idx(player_pos.x, player_pos.y);
+                        if group.is_some() {
+                            if let Some(spot) = open_spot_near(&map, *player_pos, monster_pos) {
+                                goal = spot;
+                            }
+                        }
                         let path = rltk::a_star_search(
                             map.xy_idx(pos.x, pos.y) as i32,
-                            map.xy_idx(player_pos.x, player_pos.y) as i32,
-                            &mut *map,
+                            goal as i32,
+                            &*map,
                         );
                         if path.success && path.steps.len() > 1 {
-                            let mut idx = map.xy_idx(pos.x, pos.y);
-                            map.blocked[idx] = false;
-                            pos.x = path.steps[1] as i32 % map.width;
-                            pos.y = path.steps[1] as i32 / map.width;
-                            idx = map.xy_idx(pos.x, pos.y);
-                            map.blocked[idx] = true;
-                            viewshed.dirty = true;
+                            move_monster(&mut map, pos, viewshed, path.steps[1]);
+                        }
+                    }
+                } else {
+                    // Go and check out anything we've heard
+                    let here = map.xy_idx(pos.x, pos.y);
+                    if let Some(spot) = investigating.get(entity) {
+                        let path = rltk::a_star_search(
+                            here as i32,
+                            map.xy_idx(spot.x, spot.y) as i32,
+                            &*map,
+                        );
+                        if path.success && path.steps.len() > 1 {
+                            move_monster(&mut map, pos, viewshed, path.steps[1]);
+                        } else {
+                            investigating.remove(entity);
+                        }
+                        continue;
+                    }
+
+                    // Nothing to fight, so see to our gear. Wear anything better than what
+                    // we have on, then pick up whatever is underfoot, then go looking.
+                    let upgrade = carried.iter().find(|item| {
+                        if let Some(can_equip) = equippable.get(**item) {
+                            let current = (&entities, &equipped)
+                                .join()
+                                .filter(|(_e, eq)| eq.owner == entity && eq.slot == can_equip.slot)
+                                .map(|(e, _eq)| gear_score(e))
+                                .max();
+                            current.is_none_or(|score| gear_score(**item) > score)
+                        } else {
+                            false
+                        }
+                    });
+                    if let Some(upgrade) = upgrade {
+                        wants_to_use
+                            .insert(
+                                entity,
+                                WantsToUseItem {
+                                    item: *upgrade,
+                                    target: None,
+                                },
+                            )
+                            .expect("Unable to insert intent");
+                        continue;
+                    }
+
+                    let underfoot = map.tile_content[here]
+                        .iter()
+                        .find(|item| is_wanted(**item) && !claimed_items.contains(item))
+                        .copied();
+                    if let Some(item) = underfoot {
+                        wants_pickup
+                            .insert(
+                                entity,
+                                WantsToPickupItem {
+                                    collected_by: entity,
+                                    item,
+                                },
+                            )
+                            .expect("Unable to insert want to pickup");
+                        claimed_items.push(item);
+                        continue;
+                    }
+
+                    // Stragglers rejoin their pack before wandering off after loot
+                    if let Some(leader_pos) = group.and_then(|g| leader_positions.get(&g.leader)) {
+                        let distance =
+                            rltk::DistanceAlg::Pythagoras.distance2d(monster_pos, *leader_pos);
+                        if distance > 3.0 {
+                            let path = rltk::a_star_search(
+                                here as i32,
+                                map.xy_idx(leader_pos.x, leader_pos.y) as i32,
+                                &*map,
+                            );
+                            if path.success && path.steps.len() > 2 {
+                                move_monster(&mut map, pos, viewshed, path.steps[1]);
+                            }
+                            continue;
+                        }
+                    }
+
+                    let mut nearest: Option<(usize, f32)> = None;
+                    for tile in viewshed.visible_tiles.iter() {
+                        let idx = map.xy_idx(tile.x, tile.y);
+                        let has_loot = map.tile_content[idx]
+                            .iter()
+                            .any(|item| is_wanted(*item) && !claimed_items.contains(item));
+                        if has_loot {
+                            let distance =
+                                rltk::DistanceAlg::Pythagoras.distance2d(monster_pos, *tile);
+                            if nearest.is_none_or(|(_idx, best)| distance < best) {
+                                nearest = Some((idx, distance));
+                            }
+                        }
+                    }
+                    if let Some((target_idx, _distance)) = nearest {
+                        let path = rltk::a_star_search(here as i32, target_idx as i32, &*map);
+                        if path.success && path.steps.len() > 1 {
+                            move_monster(&mut map, pos, viewshed, path.steps[1]);
                         }
                     }
                 }
@@ -85,3 +327,66 @@ impl<'a> System<'a> for MonsterAI {
         }
     }
 }
+
+/// Moves a monster onto the given map index, keeping the blocked list up to date.
+pub fn move_monster(
+    map: &mut Map,
+    pos: &mut Position,
+    viewshed: &mut Viewshed,
+    destination: usize,
+) {
+    let mut idx = map.xy_idx(pos.x, pos.y);
+    map.blocked[idx] = false;
+    pos.x = destination as i32 % map.width;
+    pos.y = destination as i32 / map.width;
+    idx = map.xy_idx(pos.x, pos.y);
+    map.blocked[idx] = true;
+    viewshed.dirty = true;
+}
+
+/// Picks the open neighbouring tile that puts the most distance between `pos` and
+/// `threat`, if any of them is further away than where we're standing now.
+fn step_away(map: &Map, pos: Point, threat: Point) -> Option<usize> {
+    let mut best = None;
+    let mut best_distance = rltk::DistanceAlg::Pythagoras.distance2d(pos, threat);
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            let candidate = Point::new(pos.x + dx, pos.y + dy);
+            if candidate.x < 1
+                || candidate.x > map.width - 2
+                || candidate.y < 1
+                || candidate.y > map.height - 2
+            {
+                continue;
+            }
+            let idx = map.xy_idx(candidate.x, candidate.y);
+            let distance = rltk::DistanceAlg::Pythagoras.distance2d(candidate, threat);
+            if !map.blocked[idx] && distance > best_distance {
+                best = Some(idx);
+                best_distance = distance;
+            }
+        }
+    }
+    best
+}
+
+/// Picks the unblocked tile next to `target` that is closest to `from`.
+fn open_spot_near(map: &Map, target: Point, from: Point) -> Option<usize> {
+    let mut best = None;
+    let mut best_distance = f32::MAX;
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            let candidate = Point::new(target.x + dx, target.y + dy);
+            if candidate == target {
+                continue;
+            }
+            let idx = map.xy_idx(candidate.x, candidate.y);
+            let distance = rltk::DistanceAlg::Pythagoras.distance2d(candidate, from);
+            if !map.blocked[idx] && distance < best_distance {
+                best = Some(idx);
+                best_distance = distance;
+            }
+        }
+    }
+    best
+}
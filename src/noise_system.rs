@@ -0,0 +1,136 @@
+use super::{Asleep, GameLog, Investigating, Map, Monster, Name, Noticed, Position, TileType};
+use specs::prelude::*;
+use std::collections::BinaryHeap;
+
+pub const COMBAT_VOLUME: i32 = 8;
+pub const EXPLOSION_VOLUME: i32 = 12;
+/// Loud enough that a careless step wakes sleepers a few tiles away; stealth
+/// takes a point off per level.
+pub const FOOTSTEP_VOLUME: i32 = 8;
+pub const CLATTER_VOLUME: i32 = 6;
+
+/// How much extra a wall tile muffles a sound passing through it.
+const WALL_ATTENUATION: i32 = 4;
+/// Sleeping monsters only wake up for sounds at least this loud where they lie.
+const WAKE_THRESHOLD: i32 = 2;
+
+pub struct Noise {
+    pub x: i32,
+    pub y: i32,
+    pub volume: i32,
+}
+
+#[derive(Default)]
+pub struct NoiseEvents {
+    pub events: Vec<Noise>,
+}
+
+impl NoiseEvents {
+    pub fn make_noise(&mut self, x: i32, y: i32, volume: i32) {
+        if volume > 0 {
+            self.events.push(Noise { x, y, volume });
+        }
+    }
+}
+
+pub struct NoiseSystem {}
+
+impl<'a> System<'a> for NoiseSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        ReadExpect<'a, Map>,
+        WriteExpect<'a, NoiseEvents>,
+        WriteExpect<'a, GameLog>,
+        Entities<'a>,
+        ReadStorage<'a, Monster>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, Noticed>,
+        WriteStorage<'a, Asleep>,
+        WriteStorage<'a, Investigating>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            map,
+            mut noise_events,
+            mut log,
+            entities,
+            monsters,
+            positions,
+            names,
+            noticed,
+            mut asleep,
+            mut investigating,
+        ) = data;
+
+        for noise in noise_events.events.drain(..) {
+            let loudness = propagate(&map, &noise);
+            for (entity, _monster, pos) in (&entities, &monsters, &positions).join() {
+                let heard = loudness[map.xy_idx(pos.x, pos.y)];
+                if heard <= 0 {
+                    continue;
+                }
+                if asleep.get(entity).is_some() {
+                    if heard < WAKE_THRESHOLD {
+                        continue;
+                    }
+                    asleep.remove(entity);
+                    if map.visible_tiles[map.xy_idx(pos.x, pos.y)] {
+                        if let Some(name) = names.get(entity) {
+                            log.entries.push(format!("{} wakes up.", name.name));
+                        }
+                    }
+                }
+                // Anything not already hunting the player goes to see what the fuss is about
+                if noticed.get(entity).is_none() {
+                    investigating
+                        .insert(
+                            entity,
+                            Investigating {
+                                x: noise.x,
+                                y: noise.y,
+                            },
+                        )
+                        .expect("Unable to insert investigation");
+                }
+            }
+        }
+    }
+}
+
+/// Spreads a noise out across the map, returning how loud it is at every tile. Sound
+/// loses one point of volume per tile travelled, and a lot more going through walls.
+fn propagate(map: &Map, noise: &Noise) -> Vec<i32> {
+    let mut loudness = vec![0; map.tiles.len()];
+    let start = map.xy_idx(noise.x, noise.y);
+    loudness[start] = noise.volume;
+
+    let mut open = BinaryHeap::new();
+    open.push((noise.volume, start));
+    while let Some((volume, idx)) = open.pop() {
+        if volume < loudness[idx] {
+            continue;
+        }
+        let x = idx as i32 % map.width;
+        let y = idx as i32 / map.width;
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let (nx, ny) = (x + dx, y + dy);
+                if nx < 0 || nx >= map.width || ny < 0 || ny >= map.height {
+                    continue;
+                }
+                let next = map.xy_idx(nx, ny);
+                let mut remaining = volume - 1;
+                if map.tiles[next] == TileType::Wall {
+                    remaining -= WALL_ATTENUATION;
+                }
+                if remaining > loudness[next] {
+                    loudness[next] = remaining;
+                    open.push((remaining, next));
+                }
+            }
+        }
+    }
+    loudness
+}
@@ -0,0 +1,123 @@
+use super::{
+    monster_ai_system::move_monster, Grudge, Map, Monster, Pet, Position, RunState, StatusEffect,
+    StatusEffects, Viewshed, WantsToMelee,
+};
+use rltk::Point;
+use specs::prelude::*;
+
+pub struct PetAI {}
+
+impl<'a> System<'a> for PetAI {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        WriteExpect<'a, Map>,
+        ReadExpect<'a, Point>,
+        ReadExpect<'a, RunState>,
+        Entities<'a>,
+        WriteStorage<'a, Viewshed>,
+        ReadStorage<'a, Pet>,
+        ReadStorage<'a, Monster>,
+        WriteStorage<'a, Position>,
+        WriteStorage<'a, WantsToMelee>,
+        ReadStorage<'a, StatusEffects>,
+        ReadStorage<'a, Grudge>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            mut map,
+            player_pos,
+            runstate,
+            entities,
+            mut viewshed,
+            pets,
+            monsters,
+            mut position,
+            mut wants_to_melee,
+            statuses,
+            grudges,
+        ) = data;
+
+        if *runstate != RunState::MonsterTurn {
+            return;
+        }
+
+        // Where each pet's grudge target is standing
+        let grudge_targets: Vec<(Entity, Entity, Point)> = (&entities, &grudges)
+            .join()
+            .filter_map(|(pet, grudge)| {
+                position
+                    .get(grudge.against)
+                    .map(|pos| (pet, grudge.against, Point::new(pos.x, pos.y)))
+            })
+            .collect();
+
+        for (entity, viewshed, _pet, pos) in (&entities, &mut viewshed, &pets, &mut position).join()
+        {
+            if statuses
+                .get(entity)
+                .is_some_and(|s| s.has(StatusEffect::Confused) || s.loses_turn())
+            {
+                continue;
+            }
+
+            // Go after whoever hurt us or the player, if we can see them
+            let grudge = grudge_targets.iter().find(|(pet, _target, target_pos)| {
+                *pet == entity && viewshed.visible_tiles.contains(target_pos)
+            });
+            if let Some((_pet, target, target_pos)) = grudge {
+                let distance =
+                    rltk::DistanceAlg::Pythagoras.distance2d(Point::new(pos.x, pos.y), *target_pos);
+                if distance < 1.5 {
+                    wants_to_melee
+                        .insert(entity, WantsToMelee { target: *target })
+                        .expect("Unable to insert attack");
+                    continue;
+                }
+                let path = rltk::a_star_search(
+                    map.xy_idx(pos.x, pos.y) as i32,
+                    map.xy_idx(target_pos.x, target_pos.y) as i32,
+                    &*map,
+                );
+                if path.success && path.steps.len() > 2 {
+                    move_monster(&mut map, pos, viewshed, path.steps[1]);
+                    continue;
+                }
+            }
+
+            // Bite anything hostile that's next to us
+            let mut target: Option<Entity> = None;
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let idx = map.xy_idx(pos.x + dx, pos.y + dy);
+                    for mob in map.tile_content[idx].iter() {
+                        if monsters.get(*mob).is_some() {
+                            target = Some(*mob);
+                        }
+                    }
+                }
+            }
+            if let Some(target) = target {
+                wants_to_melee
+                    .insert(entity, WantsToMelee { target })
+                    .expect("Unable to insert attack");
+                continue;
+            }
+
+            // Otherwise stay at the player's heel
+            let distance =
+                rltk::DistanceAlg::Pythagoras.distance2d(Point::new(pos.x, pos.y), *player_pos);
+            if distance > 1.5 {
+                let path = rltk::a_star_search(
+                    map.xy_idx(pos.x, pos.y) as i32,
+                    map.xy_idx(player_pos.x, player_pos.y) as i32,
+                    &*map,
+                );
+                // The last step is the player's own tile, so stop short of it
+                if path.success && path.steps.len() > 2 {
+                    move_monster(&mut map, pos, viewshed, path.steps[1]);
+                }
+            }
+        }
+    }
+}
@@ -1,14 +1,63 @@
-use rltk::{Point, Rltk, VirtualKeyCode};
+use rltk::{Point, RandomNumberGenerator, Rltk, VirtualKeyCode};
 use specs::prelude::*;
 use std::cmp::{max, min};
 
 use super::{
-    CombatStats, GameLog, Item, Map, Monster, Player, Position, RunState, State, TileType,
-    Viewshed, WantsToMelee,
+    noise_system::{NoiseEvents, FOOTSTEP_VOLUME},
+    shop, Ammunition, Attributes, CombatStats, Encumbrance, Equipped, GameClock, GameLog,
+    InBackpack, Item, Map, Pet, Player, Position, RangedWeapon, RunState, State, StatusEffect,
+    StatusEffects, Stealth, TileType, Viewshed, WantsToMelee,
 };
 use crate::WantsToPickupItem;
 
+/// An overloaded player only manages to move every other turn.
+fn player_staggers(ecs: &World) -> bool {
+    let player_entity = ecs.fetch::<Entity>();
+    let encumbrance = ecs.read_storage::<Encumbrance>();
+    let attributes = ecs.read_storage::<Attributes>();
+    let overloaded = match (
+        encumbrance.get(*player_entity),
+        attributes.get(*player_entity),
+    ) {
+        (Some(load), Some(attr)) => load.carried > attr.carry_capacity(),
+        _ => false,
+    };
+    overloaded && ecs.fetch::<GameClock>().turn % 2 == 1
+}
+
 pub fn try_move_player(delta_x: i32, delta_y: i32, ecs: &mut World) {
+    if player_staggers(ecs) {
+        ecs.write_resource::<GameLog>()
+            .entries
+            .push("You stagger under the weight of your pack.".to_string());
+        return;
+    }
+
+    // A confused player staggers off in a random direction half the time
+    let (delta_x, delta_y) = {
+        let player_entity = ecs.fetch::<Entity>();
+        let statuses = ecs.read_storage::<StatusEffects>();
+        let confused = statuses
+            .get(*player_entity)
+            .is_some_and(|s| s.has(StatusEffect::Confused));
+        let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+        if confused && rng.roll_dice(1, 2) == 1 {
+            let (mut dx, mut dy) = (0, 0);
+            while dx == 0 && dy == 0 {
+                dx = rng.roll_dice(1, 3) - 2;
+                dy = rng.roll_dice(1, 3) - 2;
+            }
+            if (dx, dy) != (delta_x, delta_y) {
+                let mut log = ecs.fetch_mut::<GameLog>();
+                log.entries
+                    .push("You stumble about in confusion!".to_string());
+            }
+            (dx, dy)
+        } else {
+            (delta_x, delta_y)
+        }
+    };
+
     let mut positions = ecs.write_storage::<Position>();
     let mut players = ecs.write_storage::<Player>();
     let mut viewsheds = ecs.write_storage::<Viewshed>();
@@ -16,6 +65,8 @@ pub fn try_move_player(delta_x: i32, delta_y: i32, ecs: &mut World) {
     let map = ecs.fetch::<Map>();
     let entities = ecs.entities();
     let mut wants_to_melee = ecs.write_storage::<WantsToMelee>();
+    let pets = ecs.read_storage::<Pet>();
+    let mut swapped: Option<(Entity, i32, i32)> = None;
 
     for (entity, _player, pos, viewshed) in
         (&entities, &mut players, &mut positions, &mut viewsheds).join()
@@ -30,6 +81,11 @@ pub fn try_move_player(delta_x: i32, delta_y: i32, ecs: &mut World) {
         let destination_idx = map.xy_idx(pos.x + delta_x, pos.y + delta_y);
 
         for potential_target in map.tile_content[destination_idx].iter() {
+            // Walking into the dog trades places with it rather than attacking it
+            if pets.get(*potential_target).is_some() {
+                swapped = Some((*potential_target, pos.x, pos.y));
+                break;
+            }
             let target = combat_stats.get(*potential_target);
             if let Some(_target) = target {
                 wants_to_melee
@@ -43,13 +99,28 @@ pub fn try_move_player(delta_x: i32, delta_y: i32, ecs: &mut World) {
                 return;
             }
         }
-        if !map.blocked[destination_idx] {
+        if !map.blocked[destination_idx] || swapped.is_some() {
             pos.x = min(79, max(0, pos.x + delta_x));
             pos.y = min(49, max(0, pos.y + delta_y));
             let mut ppos = ecs.write_resource::<Point>();
             ppos.x = pos.x;
             ppos.y = pos.y;
             viewshed.dirty = true;
+
+            let stealth = ecs.read_storage::<Stealth>();
+            let sneakiness = stealth.get(entity).map_or(0, |s| s.level);
+            let mut noise = ecs.write_resource::<NoiseEvents>();
+            noise.make_noise(pos.x, pos.y, FOOTSTEP_VOLUME - sneakiness);
+        }
+    }
+
+    if let Some((pet, x, y)) = swapped {
+        if let Some(pet_pos) = positions.get_mut(pet) {
+            pet_pos.x = x;
+            pet_pos.y = y;
+        }
+        if let Some(pet_viewshed) = viewsheds.get_mut(pet) {
+            pet_viewshed.dirty = true;
         }
     }
 }
@@ -103,35 +174,58 @@ pub fn try_next_level(ecs: &mut World) -> bool {
     }
 }
 
-pub fn skip_turn(ecs: &mut World) -> RunState {
+/// Brings up targeting for the equipped ranged weapon, if there's anything to fire.
+fn fire_ranged_weapon(ecs: &mut World) -> RunState {
     let player_entity = ecs.fetch::<Entity>();
-    let viewshed_components = ecs.read_storage::<Viewshed>();
-    let monsters = ecs.read_storage::<Monster>();
-
-    let worldmap_resource = ecs.fetch::<Map>();
-
-    let mut can_heal = true;
-    let viewshed = viewshed_components.get(*player_entity).unwrap();
-
-    for tile in viewshed.visible_tiles.iter() {
-        let idx = worldmap_resource.xy_idx(tile.x, tile.y);
-        for entity_id in worldmap_resource.tile_content[idx].iter() {
-            let mob = monsters.get(*entity_id);
-            match mob {
-                None => {}
-                Some(_) => {
-                    can_heal = false;
+    let entities = ecs.entities();
+    let equipped = ecs.read_storage::<Equipped>();
+    let ranged_weapons = ecs.read_storage::<RangedWeapon>();
+    let ammunition = ecs.read_storage::<Ammunition>();
+    let backpack = ecs.read_storage::<InBackpack>();
+    let mut gamelog = ecs.fetch_mut::<GameLog>();
+
+    let weapon = (&ranged_weapons, &equipped)
+        .join()
+        .find(|(_weapon, equipped_by)| equipped_by.owner == *player_entity)
+        .map(|(weapon, _equipped_by)| weapon);
+    match weapon {
+        None => {
+            gamelog
+                .entries
+                .push("You have nothing to fire.".to_string());
+            RunState::AwaitingInput
+        }
+        Some(weapon) => {
+            let has_ammo = (&entities, &ammunition, &backpack)
+                .join()
+                .any(|(_e, ammo, pack)| pack.owner == *player_entity && ammo.kind == weapon.ammo);
+            if has_ammo {
+                RunState::ShowFireTarget {
+                    range: weapon.range,
                 }
+            } else {
+                gamelog
+                    .entries
+                    .push(format!("You're out of {}!", weapon.ammo));
+                RunState::AwaitingInput
             }
         }
     }
+}
 
-    if can_heal {
-        let mut health_components = ecs.write_storage::<CombatStats>();
-        let player_hp = health_components.get_mut(*player_entity).unwrap();
-        player_hp.hp = i32::min(player_hp.hp + 1, player_hp.max_hp);
+fn trade_with_vendor(ecs: &mut World) -> RunState {
+    match shop::vendor_next_to_player(ecs) {
+        Some(vendor) => RunState::ShowShop {
+            vendor,
+            selling: false,
+        },
+        None => {
+            ecs.write_resource::<GameLog>()
+                .entries
+                .push("There's nobody here to trade with.".to_string());
+            RunState::AwaitingInput
+        }
     }
-    RunState::PlayerTurn
 }
 
 pub fn player_input(gs: &mut State, ctx: &mut Rltk) -> RunState {
@@ -159,7 +253,7 @@ pub fn player_input(gs: &mut State, ctx: &mut Rltk) -> RunState {
 
             // Skip Turn
             VirtualKeyCode::Numpad5 | VirtualKeyCode::Space | VirtualKeyCode::Key5 => {
-                return skip_turn(&mut gs.ecs)
+                return RunState::PlayerTurn
             }
 
             // Diagonals
@@ -181,7 +275,13 @@ pub fn player_input(gs: &mut State, ctx: &mut Rltk) -> RunState {
             // Inventory
             VirtualKeyCode::I => return RunState::ShowInventory,
             VirtualKeyCode::D => return RunState::ShowDropItem,
-            VirtualKeyCode::R => return RunState::ShowRemoveItem,
+            VirtualKeyCode::R => return RunState::ShowEquipment,
+            VirtualKeyCode::T => return RunState::ShowThrowItem,
+            VirtualKeyCode::C => return RunState::ShowCookMenu,
+            VirtualKeyCode::V => return trade_with_vendor(&mut gs.ecs),
+
+            // Ranged weapon
+            VirtualKeyCode::F => return fire_ranged_weapon(&mut gs.ecs),
 
             // Stairs
             VirtualKeyCode::Period => {
@@ -0,0 +1,141 @@
+use super::{
+    attr_bonus,
+    gamelog::GameLog,
+    noise_system::{NoiseEvents, COMBAT_VOLUME},
+    Ammunition, Attributes, CombatStats, Equipped, InBackpack, Map, Name, Position, RangedWeapon,
+    Stackable, SufferDamage, TileType, WantsToShoot,
+};
+use rltk::{LineAlg, Point, RandomNumberGenerator};
+use specs::prelude::*;
+
+pub struct RangedCombatSystem {}
+
+impl<'a> System<'a> for RangedCombatSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, Map>,
+        Entities<'a>,
+        WriteStorage<'a, WantsToShoot>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, CombatStats>,
+        WriteStorage<'a, SufferDamage>,
+        WriteExpect<'a, GameLog>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, Equipped>,
+        ReadStorage<'a, RangedWeapon>,
+        ReadStorage<'a, Ammunition>,
+        WriteStorage<'a, Stackable>,
+        ReadStorage<'a, InBackpack>,
+        ReadStorage<'a, Attributes>,
+        WriteExpect<'a, NoiseEvents>,
+        WriteExpect<'a, RandomNumberGenerator>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            player_entity,
+            map,
+            entities,
+            mut wants_shoot,
+            names,
+            combat_stats,
+            mut inflict_damage,
+            mut log,
+            positions,
+            equipped,
+            ranged_weapons,
+            ammunition,
+            mut stacks,
+            backpack,
+            attributes,
+            mut noise,
+            mut rng,
+        ) = data;
+
+        for (entity, wants_shoot, name, pos) in (&entities, &wants_shoot, &names, &positions).join()
+        {
+            let is_player = entity == *player_entity;
+            let weapon = (&entities, &ranged_weapons, &equipped)
+                .join()
+                .find(|(_e, _weapon, equipped_by)| equipped_by.owner == entity)
+                .map(|(e, weapon, _equipped_by)| (e, weapon));
+            let (weapon_entity, weapon) = match weapon {
+                Some(weapon) => weapon,
+                None => continue,
+            };
+
+            // Use up one shot from the first matching pile in the backpack
+            let ammo = (&entities, &ammunition, &mut stacks, &backpack)
+                .join()
+                .find(|(_e, ammo, _stack, pack)| pack.owner == entity && ammo.kind == weapon.ammo)
+                .map(|(e, _ammo, stack, _pack)| (e, stack));
+            match ammo {
+                None => {
+                    if is_player {
+                        log.entries.push(format!("You're out of {}!", weapon.ammo));
+                    }
+                    continue;
+                }
+                Some((ammo_entity, stack)) => {
+                    stack.quantity -= 1;
+                    if stack.quantity < 1 {
+                        entities.delete(ammo_entity).expect("Delete failed");
+                    }
+                }
+            }
+
+            noise.make_noise(pos.x, pos.y, COMBAT_VOLUME);
+            let weapon_name = &names.get(weapon_entity).unwrap().name;
+
+            // The shot flies along a line and stops at the first wall or creature
+            let mut hit_something = false;
+            let path = rltk::line2d(
+                LineAlg::Bresenham,
+                Point::new(pos.x, pos.y),
+                wants_shoot.target,
+            );
+            for tile in path.iter().skip(1) {
+                let idx = map.xy_idx(tile.x, tile.y);
+                if map.tiles[idx] == TileType::Wall {
+                    if is_player {
+                        log.entries
+                            .push(format!("The {} splats against the wall.", weapon.ammo));
+                    }
+                    hit_something = true;
+                    break;
+                }
+                let victim = map.tile_content[idx]
+                    .iter()
+                    .find(|mob| **mob != entity && combat_stats.get(**mob).is_some());
+                if let Some(victim) = victim {
+                    let agility = attributes.get(entity).map_or(10, |a| a.agility);
+                    let damage = i32::max(1, rng.roll(weapon.damage) + attr_bonus(agility));
+                    log.entries.push(format!(
+                        "{} shoots {} with the {}, for {} hp.",
+                        &name.name,
+                        &names.get(*victim).unwrap().name,
+                        weapon_name,
+                        damage
+                    ));
+                    SufferDamage::new_damage(
+                        &mut inflict_damage,
+                        *victim,
+                        damage,
+                        weapon.damage_type,
+                        entity,
+                        Some(weapon_entity),
+                    );
+                    hit_something = true;
+                    break;
+                }
+            }
+            if !hit_something && is_player {
+                log.entries
+                    .push(format!("The {} lands harmlessly.", weapon.ammo));
+            }
+        }
+
+        wants_shoot.clear();
+    }
+}
@@ -0,0 +1,93 @@
+use super::{
+    attr_bonus, Asleep, Attributes, CombatStats, GameClock, Grudge, HungerClock, HungerState,
+    Monster, Noticed, Player, Position, RunState, Viewshed,
+};
+use rltk::Point;
+use specs::prelude::*;
+
+pub struct RegenerationSystem {}
+
+impl<'a> System<'a> for RegenerationSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        ReadExpect<'a, GameClock>,
+        ReadExpect<'a, RunState>,
+        ReadExpect<'a, super::map::Map>,
+        Entities<'a>,
+        WriteStorage<'a, CombatStats>,
+        ReadStorage<'a, Attributes>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Viewshed>,
+        ReadStorage<'a, Monster>,
+        ReadStorage<'a, Asleep>,
+        ReadStorage<'a, Noticed>,
+        ReadStorage<'a, Grudge>,
+        ReadStorage<'a, HungerClock>,
+        ReadStorage<'a, Position>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            clock,
+            runstate,
+            map,
+            entities,
+            mut combat_stats,
+            attributes,
+            players,
+            viewsheds,
+            monsters,
+            asleep,
+            noticed,
+            grudges,
+            hunger_clocks,
+            positions,
+        ) = data;
+
+        if *runstate != RunState::MonsterTurn {
+            return;
+        }
+
+        for (entity, stats) in (&entities, &mut combat_stats).join() {
+            if stats.hp < 1 || stats.hp >= stats.max_hp {
+                continue;
+            }
+
+            // Nobody catches their breath mid-fight. The player is fighting whenever an
+            // awake monster is in view; everyone else while hunting, or while whoever
+            // they hold a grudge against is still around and in sight.
+            let in_combat = if players.get(entity).is_some() {
+                viewsheds.get(entity).is_some_and(|viewshed| {
+                    viewshed.visible_tiles.iter().any(|tile| {
+                        map.tile_content[map.xy_idx(tile.x, tile.y)]
+                            .iter()
+                            .any(|e| monsters.get(*e).is_some() && asleep.get(*e).is_none())
+                    })
+                })
+            } else {
+                noticed.get(entity).is_some()
+                    || grudges.get(entity).is_some_and(|grudge| {
+                        entities.is_alive(grudge.against)
+                            && positions.get(grudge.against).is_some_and(|pos| {
+                                viewsheds.get(entity).is_some_and(|viewshed| {
+                                    viewshed.visible_tiles.contains(&Point::new(pos.x, pos.y))
+                                })
+                            })
+                    })
+            };
+            let hungry = hunger_clocks.get(entity).is_some_and(|h| {
+                h.state == HungerState::Hungry || h.state == HungerState::Starving
+            });
+            if in_combat || hungry {
+                continue;
+            }
+
+            // Tougher creatures heal more often, and bigger ones heal more at a time
+            let toughness = attributes.get(entity).map_or(10, |a| a.toughness);
+            let interval = i32::max(2, 8 - attr_bonus(toughness) * 2);
+            if clock.turn % interval == 0 {
+                stats.hp = i32::min(stats.max_hp, stats.hp + 1 + stats.max_hp / 50);
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use super::components::*;
+use super::identification::Identification;
 use specs::error::NoError;
 use specs::prelude::*;
 use specs::saveload::{
@@ -41,9 +42,15 @@ macro_rules! deserialize_individually {
 pub fn save_game(ecs: &mut World) {
     // Create helper
     let mapcopy = ecs.get_mut::<super::map::Map>().unwrap().clone();
+    let turn = ecs.fetch::<super::GameClock>().turn;
+    let identification = (*ecs.fetch::<Identification>()).clone();
     let savehelper = ecs
         .create_entity()
-        .with(SerializationHelper { map: mapcopy })
+        .with(SerializationHelper {
+            map: mapcopy,
+            turn,
+            identification,
+        })
         .marked::<SimpleMarker<SerializeMe>>()
         .build();
 
@@ -68,14 +75,13 @@ pub fn save_game(ecs: &mut World) {
             Name,
             BlocksTile,
             CombatStats,
-            SufferDamage,
             WantsToMelee,
             Item,
             Consumable,
             Ranged,
             InflictsDamage,
             AreaOfEffect,
-            Confusion,
+            InflictsStatus,
             ProvidesHealing,
             InBackpack,
             WantsToPickupItem,
@@ -86,7 +92,48 @@ pub fn save_game(ecs: &mut World) {
             Equipped,
             MeleePowerBonus,
             DefenseBonus,
-            WantsToRemoveItem
+            WantsToRemoveItem,
+            RangedAttacker,
+            Pet,
+            GroupMember,
+            Asleep,
+            Noticed,
+            Investigating,
+            Stealth,
+            MeleeWeapon,
+            NaturalAttack,
+            Experience,
+            Attributes,
+            Skills,
+            Resistant,
+            Vulnerable,
+            StatusEffects,
+            RangedWeapon,
+            Ammunition,
+            WantsToShoot,
+            ThrowDamage,
+            Fragile,
+            WantsToThrowItem,
+            HungerClock,
+            ProvidesFood,
+            Ingredient,
+            Stove,
+            LootTable,
+            Unidentified,
+            IdentifiesItem,
+            Stackable,
+            Weight,
+            Encumbrance,
+            TwoHanded,
+            Cursed,
+            RemovesCurse,
+            Charges,
+            WeaponAffix,
+            Value,
+            Gold,
+            Purse,
+            Vendor,
+            KnownClean
         );
     }
 
@@ -135,14 +182,13 @@ pub fn load_game(ecs: &mut World) {
             Name,
             BlocksTile,
             CombatStats,
-            SufferDamage,
             WantsToMelee,
             Item,
             Consumable,
             Ranged,
             InflictsDamage,
             AreaOfEffect,
-            Confusion,
+            InflictsStatus,
             ProvidesHealing,
             InBackpack,
             WantsToPickupItem,
@@ -153,7 +199,48 @@ pub fn load_game(ecs: &mut World) {
             Equipped,
             MeleePowerBonus,
             DefenseBonus,
-            WantsToRemoveItem
+            WantsToRemoveItem,
+            RangedAttacker,
+            Pet,
+            GroupMember,
+            Asleep,
+            Noticed,
+            Investigating,
+            Stealth,
+            MeleeWeapon,
+            NaturalAttack,
+            Experience,
+            Attributes,
+            Skills,
+            Resistant,
+            Vulnerable,
+            StatusEffects,
+            RangedWeapon,
+            Ammunition,
+            WantsToShoot,
+            ThrowDamage,
+            Fragile,
+            WantsToThrowItem,
+            HungerClock,
+            ProvidesFood,
+            Ingredient,
+            Stove,
+            LootTable,
+            Unidentified,
+            IdentifiesItem,
+            Stackable,
+            Weight,
+            Encumbrance,
+            TwoHanded,
+            Cursed,
+            RemovesCurse,
+            Charges,
+            WeaponAffix,
+            Value,
+            Gold,
+            Purse,
+            Vendor,
+            KnownClean
         );
     }
 
@@ -167,6 +254,8 @@ pub fn load_game(ecs: &mut World) {
             let mut worldmap = ecs.write_resource::<super::map::Map>();
             *worldmap = h.map.clone();
             worldmap.tile_content = vec![Vec::new(); super::map::MAPCOUNT];
+            ecs.write_resource::<super::GameClock>().turn = h.turn;
+            *ecs.write_resource::<Identification>() = h.identification.clone();
             deleteme = Some(e);
         }
         for (e, _p, pos) in (&entities, &player, &position).join() {
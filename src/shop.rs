@@ -0,0 +1,140 @@
+use super::{
+    gamelog::GameLog,
+    inventory_system::{adjust_load, can_carry, split_stack},
+    Charges, InBackpack, Name, Position, Purse, Stackable, Value, Vendor, Weight,
+};
+use rltk::Point;
+use serde::Deserialize;
+use specs::prelude::*;
+use std::collections::HashMap;
+
+const PRICE_DATA: &str = include_str!("../resources/prices.json");
+
+// What every kind of item is worth, loaded from resources/prices.json.
+#[derive(Deserialize)]
+pub struct PriceList {
+    pub base_values: HashMap<String, i32>,
+}
+
+impl PriceList {
+    pub fn load() -> PriceList {
+        serde_json::from_str(PRICE_DATA).expect("Unable to parse prices")
+    }
+
+    /// What one of the named item is worth; anything unlisted goes for a coin.
+    pub fn base_value(&self, name: &str) -> i32 {
+        self.base_values.get(name).copied().unwrap_or(1)
+    }
+}
+
+pub fn vendor_next_to_player(ecs: &World) -> Option<Entity> {
+    let player_pos = ecs.fetch::<Point>();
+    let entities = ecs.entities();
+    let positions = ecs.read_storage::<Position>();
+    let vendors = ecs.read_storage::<Vendor>();
+    (&entities, &positions, &vendors)
+        .join()
+        .find(|(_e, pos, _vendor)| {
+            rltk::DistanceAlg::Pythagoras.distance2d(Point::new(pos.x, pos.y), *player_pos) < 1.5
+        })
+        .map(|(e, _pos, _vendor)| e)
+}
+
+/// What one of an item is worth, with part-used wands going for less.
+fn item_value(ecs: &World, item: Entity) -> i32 {
+    let value = ecs.read_storage::<Value>().get(item).map_or(1, |v| v.gold);
+    match ecs.read_storage::<Charges>().get(item) {
+        Some(charges) if charges.max > 0 => i32::max(1, value * charges.remaining / charges.max),
+        _ => value,
+    }
+}
+
+/// What a vendor charges for one of an item.
+pub fn buy_price(ecs: &World, item: Entity) -> i32 {
+    item_value(ecs, item)
+}
+
+/// What a vendor pays for one of an item.
+pub fn sell_price(ecs: &World, item: Entity) -> i32 {
+    i32::max(1, item_value(ecs, item) / 2)
+}
+
+/// Moves one of `item` into the buyer's backpack, joining a matching stack if
+/// they have one, and pays the seller for it. Returns false if the buyer can't
+/// afford it.
+fn trade(ecs: &mut World, item: Entity, seller: Entity, buyer: Entity, price: i32) -> bool {
+    {
+        let mut purses = ecs.write_storage::<Purse>();
+        match purses.get_mut(buyer) {
+            Some(purse) if purse.gold >= price => purse.gold -= price,
+            _ => return false,
+        }
+        if let Some(purse) = purses.get_mut(seller) {
+            purse.gold += price;
+        }
+    }
+
+    let item = split_stack(ecs, item, 1);
+    let weight = ecs
+        .read_storage::<Weight>()
+        .get(item)
+        .map_or(0.0, |w| w.pounds);
+    adjust_load(ecs, seller, -weight);
+    adjust_load(ecs, buyer, weight);
+    let merged = {
+        let names = ecs.read_storage::<Name>();
+        let backpack = ecs.read_storage::<InBackpack>();
+        let mut stacks = ecs.write_storage::<Stackable>();
+        let item_name = &names.get(item).unwrap().name;
+        let is_stackable = stacks.get(item).is_some();
+        match (&names, &backpack, &mut stacks)
+            .join()
+            .find(|(name, pack, _s)| is_stackable && pack.owner == buyer && name.name == *item_name)
+        {
+            Some((_name, _pack, stack)) => {
+                stack.quantity += 1;
+                true
+            }
+            None => false,
+        }
+    };
+    if merged {
+        ecs.delete_entity(item).expect("Unable to delete item");
+    } else {
+        ecs.write_storage::<InBackpack>()
+            .insert(item, InBackpack { owner: buyer })
+            .expect("Unable to insert backpack entry");
+    }
+    true
+}
+
+pub fn buy(ecs: &mut World, vendor: Entity, item: Entity) {
+    let player_entity = *ecs.fetch::<Entity>();
+    let price = buy_price(ecs, item);
+    let item_name = ecs.read_storage::<Name>().get(item).unwrap().name.clone();
+    let weight = ecs
+        .read_storage::<Weight>()
+        .get(item)
+        .map_or(0.0, |w| w.pounds);
+    let message = if !can_carry(ecs, player_entity, weight) {
+        format!("The {} is too heavy for you to carry.", item_name)
+    } else if trade(ecs, item, vendor, player_entity, price) {
+        format!("You buy the {} for {} gold.", item_name, price)
+    } else {
+        format!("You can't afford the {}.", item_name)
+    };
+    ecs.write_resource::<GameLog>().entries.push(message);
+}
+
+pub fn sell(ecs: &mut World, vendor: Entity, item: Entity) {
+    let player_entity = *ecs.fetch::<Entity>();
+    let price = sell_price(ecs, item);
+    let item_name = ecs.read_storage::<Name>().get(item).unwrap().name.clone();
+    let message = if trade(ecs, item, player_entity, vendor, price) {
+        format!("You sell the {} for {} gold.", item_name, price)
+    } else {
+        let vendor_name = ecs.read_storage::<Name>().get(vendor).unwrap().name.clone();
+        format!("The {} can't afford the {}.", vendor_name, item_name)
+    };
+    ecs.write_resource::<GameLog>().entries.push(message);
+}
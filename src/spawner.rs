@@ -1,19 +1,37 @@
 use super::{
-    random_table::RandomTable, AreaOfEffect, BlocksTile, CombatStats, Confusion, Consumable,
-    InflictsDamage, Item, Monster, Name, Player, Position, ProvidesHealing, Ranged, Rect,
-    Renderable, SerializeMe, Viewshed, MAPWIDTH,
+    cookbook::{Cookbook, Recipe},
+    hunger_system::{NORMAL_TURNS, WELL_FED_TURNS},
+    identification::Identification,
+    random_table::RandomTable,
+    shop::PriceList,
+    Affix, Ammunition, AreaOfEffect, Asleep, Attributes, BlocksTile, Charges, CombatStats,
+    Consumable, Cursed, DamageType, Experience, Fragile, Gold, GroupMember, HungerClock,
+    HungerState, IdentifiesItem, InBackpack, InflictsDamage, InflictsStatus, Ingredient, Item,
+    LootTable, Monster, Name, Pet, Player, Position, ProvidesFood, ProvidesHealing, Purse, Ranged,
+    RangedAttacker, RangedWeapon, Rect, RemovesCurse, Renderable, Resistant, SerializeMe, Skills,
+    Stackable, StatusEffect, StatusEffects, Stealth, Stove, ThrowDamage, TwoHanded, Unidentified,
+    Value, Vendor, Viewshed, Vulnerable, WeaponAffix, Weight, MAPWIDTH,
 };
-use crate::{DefenseBonus, EquipmentSlot, Equippable, MeleePowerBonus};
-use rltk::{RandomNumberGenerator, RGB};
+use crate::{DefenseBonus, EquipmentSlot, Equippable, MeleePowerBonus, MeleeWeapon, NaturalAttack};
+use rltk::{DiceType, RandomNumberGenerator, RGB};
 use specs::prelude::*;
 use specs::saveload::{MarkedBuilder, SimpleMarker};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 const MAX_MONSTERS: i32 = 4;
 const MAX_ITEMS: i32 = 2;
 
 /// Spawns the player and returns their entity object.
 pub fn player(ecs: &mut World, player_x: i32, player_y: i32) -> Entity {
+    let attributes = Attributes {
+        strength: 12,
+        toughness: 14,
+        agility: 12,
+        smarts: 11,
+    };
+    let max_hp = 20 + attributes.hp_bonus();
+    let mut statuses = StatusEffects::default();
+    statuses.add(StatusEffect::WellFed, WELL_FED_TURNS, None, None);
     ecs.create_entity()
         .with(Position {
             x: player_x,
@@ -35,28 +53,126 @@ pub fn player(ecs: &mut World, player_x: i32, player_y: i32) -> Entity {
             name: "Player".to_string(),
         })
         .with(CombatStats {
-            max_hp: 30,
-            hp: 30,
+            max_hp,
+            hp: max_hp,
             defense: 2,
             power: 5,
         })
+        .with(attributes)
+        .with(Skills::default())
+        .with(Stealth { level: 3 })
+        .with(HungerClock {
+            state: HungerState::Normal,
+            duration: WELL_FED_TURNS + NORMAL_TURNS,
+        })
+        .with(statuses)
+        .with(Experience { level: 1, xp: 0 })
+        .with(NaturalAttack {
+            damage: dice("1d4"),
+            damage_type: DamageType::Slashing,
+        })
+        .with(Purse { gold: 20 })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build()
+}
+
+/// Spawns the player's dog and returns its entity.
+pub fn pet(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let attributes = Attributes {
+        strength: 12,
+        toughness: 12,
+        agility: 14,
+        smarts: 8,
+    };
+    let max_hp = 15 + attributes.hp_bonus();
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Renderable {
+            glyph: 3,
+            fg: RGB::named(rltk::SANDY_BROWN),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 1,
+        })
+        .with(Pet {})
+        .with(Experience { level: 1, xp: 0 })
+        .with(Viewshed {
+            visible_tiles: Vec::new(),
+            range: 8,
+            dirty: true,
+        })
+        .with(Name {
+            name: "Rover".to_string(),
+        })
+        .with(BlocksTile {})
+        .with(CombatStats {
+            max_hp,
+            hp: max_hp,
+            defense: 1,
+            power: 4,
+        })
+        .with(attributes)
+        .with(Skills::default())
+        .with(NaturalAttack {
+            damage: dice("1d6"),
+            damage_type: DamageType::Slashing,
+        })
         .marked::<SimpleMarker<SerializeMe>>()
         .build()
 }
 
+fn dice(dice: &str) -> DiceType {
+    rltk::parse_dice_string(dice).expect("Invalid dice string")
+}
+
 fn room_table(map_depth: i32) -> RandomTable {
     RandomTable::new()
         .add("Broccoli", 10)
         .add("Potato", 1 + map_depth)
+        .add("Potato Gang", map_depth)
+        .add("Tomato", 2 + (map_depth / 2))
+        .add("Ketchup Bottle", map_depth - 1)
+        .add("Garlic Sorcerer", map_depth - 2)
         .add("Ketchup", 7)
+        .add("Dog Biscuit", 8)
+        .add("Bacon", 2 + map_depth / 2)
+        .add("Hot Sauce", 2)
+        .add("Chicken Soup", 2)
         .add("Fireball Scroll", 2 + (map_depth / 2))
         .add("Food Coma Scroll", 2 + (map_depth / 2))
         .add("Meat Beam Scroll", 4)
+        .add("Identify Scroll", 3)
+        .add("Remove Curse Scroll", 2)
+        .add("Wand of Meat Beams", map_depth - 1)
+        .add("Wand of Food Coma", map_depth - 2)
+        .add("Pepper Grinder", 2)
         .add("Spatula", 3)
         .add("Fork", map_depth - 1)
         .add("Bread Knife", map_depth - 2)
+        .add("Peashooter", 2)
+        .add("Ketchup Squirter", map_depth - 1)
+        .add("Dried Peas", 4)
+        .add("Ketchup Packets", map_depth)
+        .add("Stove", 3)
         .add("Shield", 3)
         .add("Tower Shield", map_depth - 1)
+        .add("Rolling Pin", map_depth - 1)
+        .add("Colander Helmet", 2)
+        .add("Chef's Apron", 2)
+        .add("Oven Mitts", 2)
+        .add("Fluffy Slippers", 2)
+        .add("Onion Ring", 1)
+        .add("Gold", 6)
+}
+
+fn vendor_table(map_depth: i32) -> RandomTable {
+    RandomTable::new()
+        .add("Ketchup", 5)
+        .add("Dog Biscuit", 4)
+        .add("Bacon", 4)
+        .add("Chicken Soup", 1 + map_depth / 3)
+        .add("Hot Sauce", 1 + map_depth / 3)
+        .add("Pepper Grinder", 2)
+        .add("Identify Scroll", 1)
 }
 
 #[allow(clippy::map_entry)]
@@ -86,50 +202,404 @@ pub fn spawn_room(ecs: &mut World, room: &Rect, map_depth: i32) {
         }
     }
 
+    let mut minion_tiles: HashSet<usize> = HashSet::new();
     for spawn in spawn_points.iter() {
         let x = (*spawn.0 % MAPWIDTH) as i32;
         let y = (*spawn.0 / MAPWIDTH) as i32;
 
         match spawn.1.as_ref() {
-            "Potato" => potato(ecs, x, y),
-            "Broccoli" => broccoli(ecs, x, y),
-            "Ketchup" => ketchup(ecs, x, y),
-            "Fireball Scroll" => fireball_scroll(ecs, x, y),
-            "Food Coma Scroll" => food_coma_scroll(ecs, x, y),
-            "Meat Beam Scroll" => meat_beam_scroll(ecs, x, y),
-            "Spatula" => spatula(ecs, x, y),
-            "Fork" => fork(ecs, x, y),
-            "Bread Knife" => bread_knife(ecs, x, y),
-            "Shield" => shield(ecs, x, y),
-            "Tower Shield" => tower_shield(ecs, x, y),
-            _ => {}
+            "Potato Gang" => potato_gang(ecs, room, &spawn_points, &mut minion_tiles, x, y),
+            name => spawn_named(ecs, x, y, name, map_depth),
+        }
+        roll_gear_at(ecs, x, y, map_depth);
+    }
+}
+
+fn spawn_named(ecs: &mut World, x: i32, y: i32, name: &str, map_depth: i32) {
+    match name {
+        "Potato" => {
+            potato(ecs, x, y);
+        }
+        "Broccoli" => {
+            broccoli(ecs, x, y);
+        }
+        "Tomato" => tomato(ecs, x, y),
+        "Ketchup Bottle" => ketchup_bottle(ecs, x, y),
+        "Garlic Sorcerer" => garlic_sorcerer(ecs, x, y),
+        "Ketchup" => ketchup(ecs, x, y),
+        "Dog Biscuit" => dog_biscuit(ecs, x, y),
+        "Bacon" => bacon(ecs, x, y),
+        "Hot Sauce" => hot_sauce(ecs, x, y),
+        "Chicken Soup" => chicken_soup(ecs, x, y),
+        "Fireball Scroll" => fireball_scroll(ecs, x, y),
+        "Food Coma Scroll" => food_coma_scroll(ecs, x, y),
+        "Meat Beam Scroll" => meat_beam_scroll(ecs, x, y),
+        "Identify Scroll" => identify_scroll(ecs, x, y),
+        "Remove Curse Scroll" => remove_curse_scroll(ecs, x, y),
+        "Wand of Meat Beams" => meat_beam_wand(ecs, x, y),
+        "Wand of Food Coma" => food_coma_wand(ecs, x, y),
+        "Pepper Grinder" => pepper_grinder(ecs, x, y),
+        "Spatula" => spatula(ecs, x, y),
+        "Fork" => fork(ecs, x, y),
+        "Bread Knife" => bread_knife(ecs, x, y),
+        "Peashooter" => peashooter(ecs, x, y),
+        "Ketchup Squirter" => ketchup_squirter(ecs, x, y),
+        "Dried Peas" => dried_peas(ecs, x, y),
+        "Ketchup Packets" => ketchup_packets(ecs, x, y),
+        "Stove" => stove(ecs, x, y),
+        "Shield" => shield(ecs, x, y),
+        "Tower Shield" => tower_shield(ecs, x, y),
+        "Rolling Pin" => rolling_pin(ecs, x, y),
+        "Colander Helmet" => colander_helmet(ecs, x, y),
+        "Chef's Apron" => chefs_apron(ecs, x, y),
+        "Oven Mitts" => oven_mitts(ecs, x, y),
+        "Fluffy Slippers" => fluffy_slippers(ecs, x, y),
+        "Onion Ring" => onion_ring(ecs, x, y),
+        "Gold" => gold(ecs, x, y, map_depth),
+        _ => {}
+    }
+}
+
+/// Rolls up any gear lying at a spot. Cursed gear gets its bonuses turned into
+/// penalties; the rest may be enchanted and, deeper down, weapons may get an affix.
+/// Only gear spawned through `spawn_room` gets rolled. Monsters start with nothing
+/// and only carry what they pick up off the floor, and vendors don't sell gear.
+fn roll_gear_at(ecs: &mut World, x: i32, y: i32, map_depth: i32) {
+    let entities = ecs.entities();
+    let positions = ecs.read_storage::<Position>();
+    let equippable = ecs.read_storage::<Equippable>();
+    let melee_weapons = ecs.read_storage::<MeleeWeapon>();
+    let mut names = ecs.write_storage::<Name>();
+    let mut cursed = ecs.write_storage::<Cursed>();
+    let mut affixes = ecs.write_storage::<WeaponAffix>();
+    let mut power_bonuses = ecs.write_storage::<MeleePowerBonus>();
+    let mut defense_bonuses = ecs.write_storage::<DefenseBonus>();
+    let mut values = ecs.write_storage::<Value>();
+    let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+
+    for (item, pos, _equippable, name) in (&entities, &positions, &equippable, &mut names).join() {
+        if pos.x != x || pos.y != y {
+            continue;
+        }
+        if rng.roll_dice(1, 8) == 1 {
+            cursed
+                .insert(item, Cursed { known: false })
+                .expect("Unable to insert curse");
+            if let Some(bonus) = power_bonuses.get_mut(item) {
+                bonus.power = -rng.roll_dice(1, 2);
+            }
+            if let Some(bonus) = defense_bonuses.get_mut(item) {
+                bonus.defense = -rng.roll_dice(1, 2);
+            }
+            continue;
+        }
+
+        if melee_weapons.get(item).is_some() && rng.roll_dice(1, 20) <= map_depth {
+            let affix = match rng.roll_dice(1, 3) {
+                1 => Affix::Fire,
+                2 => Affix::Speed,
+                _ => Affix::Vampiric,
+            };
+            name.name = affix.weapon_name(&name.name);
+            if let Some(value) = values.get_mut(item) {
+                value.gold += 30;
+            }
+            affixes
+                .insert(item, WeaponAffix { affix })
+                .expect("Unable to insert affix");
+        }
+
+        // Mostly better than usual, sometimes a shoddy one. Only gear with a bonus
+        // to change can be enchanted.
+        let enchantable = power_bonuses.contains(item) || defense_bonuses.contains(item);
+        if enchantable && rng.roll_dice(1, 6) <= 1 + map_depth / 3 {
+            let level = if rng.roll_dice(1, 4) == 1 {
+                -rng.roll_dice(1, 2)
+            } else {
+                rng.roll_dice(1, 1 + map_depth / 4)
+            };
+            if let Some(bonus) = power_bonuses.get_mut(item) {
+                bonus.power += level;
+            } else if let Some(bonus) = defense_bonuses.get_mut(item) {
+                bonus.defense += level;
+            }
+            name.name = format!("{:+} {}", level, name.name);
+            if let Some(value) = values.get_mut(item) {
+                value.gold = i32::max(1, value.gold + level * 20);
+            }
         }
     }
 }
 
-fn broccoli(ecs: &mut World, x: i32, y: i32) {
-    monster(ecs, x, y, 4, "Broccoli", 16, 1, 4);
+fn broccoli(ecs: &mut World, x: i32, y: i32) -> Entity {
+    monster(
+        ecs,
+        x,
+        y,
+        4,
+        RGB::named(rltk::WHITE),
+        "Broccoli",
+        16,
+        1,
+        4,
+        Attributes {
+            strength: 10,
+            toughness: 10,
+            agility: 10,
+            smarts: 8,
+        },
+    )
+    .with(Vulnerable {
+        damage_types: vec![DamageType::Fire],
+    })
+    .with(NaturalAttack {
+        damage: dice("1d4"),
+        damage_type: DamageType::Blunt,
+    })
+    .build()
+}
+
+fn potato(ecs: &mut World, x: i32, y: i32) -> Entity {
+    // Slow and dim, but hits like a sack of potatoes
+    monster(
+        ecs,
+        x,
+        y,
+        9,
+        RGB::named(rltk::WHITE),
+        "Potato",
+        25,
+        2,
+        6,
+        Attributes {
+            strength: 15,
+            toughness: 13,
+            agility: 7,
+            smarts: 6,
+        },
+    )
+    .with(Resistant {
+        damage_types: vec![DamageType::Fire, DamageType::Blunt],
+    })
+    .with(Vulnerable {
+        damage_types: vec![DamageType::Slashing],
+    })
+    .with(NaturalAttack {
+        damage: dice("1d6"),
+        damage_type: DamageType::Blunt,
+    })
+    .build()
+}
+
+/// Spawns a potato with a few broccoli minions around it, skipping tiles already
+/// spoken for by the room's other spawns or by earlier minions.
+fn potato_gang(
+    ecs: &mut World,
+    room: &Rect,
+    taken: &HashMap<usize, String>,
+    minion_tiles: &mut HashSet<usize>,
+    x: i32,
+    y: i32,
+) {
+    let leader = potato(ecs, x, y);
+    let num_minions = {
+        let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+        rng.roll_dice(1, 2) + 1
+    };
+
+    let mut members = vec![leader];
+    let offsets = [
+        (-1, 0),
+        (1, 0),
+        (0, -1),
+        (0, 1),
+        (-1, -1),
+        (1, 1),
+        (-1, 1),
+        (1, -1),
+    ];
+    for (dx, dy) in offsets.iter() {
+        if members.len() > num_minions as usize {
+            break;
+        }
+        let (mx, my) = (x + dx, y + dy);
+        let idx = (my as usize * MAPWIDTH) + mx as usize;
+        if mx <= room.x1
+            || mx > room.x2
+            || my <= room.y1
+            || my > room.y2
+            || taken.contains_key(&idx)
+            || minion_tiles.contains(&idx)
+        {
+            continue;
+        }
+        minion_tiles.insert(idx);
+        members.push(broccoli(ecs, mx, my));
+    }
+
+    let mut groups = ecs.write_storage::<GroupMember>();
+    for member in members {
+        groups
+            .insert(member, GroupMember { leader })
+            .expect("Unable to insert group member");
+    }
+}
+
+fn tomato(ecs: &mut World, x: i32, y: i32) {
+    let spit = ability(ecs, "Seed Spit")
+        .with(InflictsDamage {
+            damage: 3,
+            damage_type: DamageType::Blunt,
+        })
+        .with(InflictsStatus {
+            effect: StatusEffect::Poisoned,
+            turns: 3,
+        })
+        .build();
+    monster(
+        ecs,
+        x,
+        y,
+        9,
+        RGB::named(rltk::RED),
+        "Tomato",
+        12,
+        0,
+        3,
+        Attributes {
+            strength: 8,
+            toughness: 8,
+            agility: 15,
+            smarts: 9,
+        },
+    )
+    .with(Vulnerable {
+        damage_types: vec![DamageType::Slashing, DamageType::Cold],
+    })
+    .with(NaturalAttack {
+        damage: dice("1d4"),
+        damage_type: DamageType::Blunt,
+    })
+    .with(RangedAttacker {
+        ability: spit,
+        min_range: 2,
+        max_range: 5,
+    })
+    .build();
 }
 
-fn potato(ecs: &mut World, x: i32, y: i32) {
-    monster(ecs, x, y, 9, "Potato", 25, 2, 6);
+fn ketchup_bottle(ecs: &mut World, x: i32, y: i32) {
+    let glob = ability(ecs, "Ketchup Glob")
+        .with(InflictsDamage {
+            damage: 5,
+            damage_type: DamageType::Spice,
+        })
+        .with(InflictsStatus {
+            effect: StatusEffect::Slowed,
+            turns: 4,
+        })
+        .with(AreaOfEffect { radius: 1 })
+        .build();
+    monster(
+        ecs,
+        x,
+        y,
+        5,
+        RGB::named(rltk::ORANGE_RED),
+        "Ketchup Bottle",
+        18,
+        1,
+        4,
+        Attributes {
+            strength: 12,
+            toughness: 12,
+            agility: 9,
+            smarts: 7,
+        },
+    )
+    .with(Resistant {
+        damage_types: vec![DamageType::Spice, DamageType::Slashing],
+    })
+    .with(Vulnerable {
+        damage_types: vec![DamageType::Blunt],
+    })
+    .with(NaturalAttack {
+        damage: dice("1d4"),
+        damage_type: DamageType::Blunt,
+    })
+    .with(RangedAttacker {
+        ability: glob,
+        min_range: 3,
+        max_range: 6,
+    })
+    .build();
 }
 
+fn garlic_sorcerer(ecs: &mut World, x: i32, y: i32) {
+    let breath = ability(ecs, "Garlic Breath")
+        .with(InflictsStatus {
+            effect: StatusEffect::Confused,
+            turns: 3,
+        })
+        .build();
+    monster(
+        ecs,
+        x,
+        y,
+        4,
+        RGB::named(rltk::ANTIQUE_WHITE),
+        "Garlic Sorcerer",
+        14,
+        1,
+        3,
+        Attributes {
+            strength: 8,
+            toughness: 9,
+            agility: 11,
+            smarts: 16,
+        },
+    )
+    .with(Resistant {
+        damage_types: vec![DamageType::Spice],
+    })
+    .with(NaturalAttack {
+        damage: dice("1d4"),
+        damage_type: DamageType::Blunt,
+    })
+    .with(RangedAttacker {
+        ability: breath,
+        min_range: 3,
+        max_range: 6,
+    })
+    .build();
+}
+
+#[allow(clippy::too_many_arguments)]
 fn monster<S: ToString>(
     ecs: &mut World,
     x: i32,
     y: i32,
     glyph: u16,
+    fg: RGB,
     name: S,
     max_hp: i32,
     defense: i32,
     power: i32,
-) {
-    ecs.create_entity()
+    attributes: Attributes,
+) -> EntityBuilder<'_> {
+    // Toughness counts for monsters the same as it does for the player
+    let max_hp = i32::max(1, max_hp + attributes.hp_bonus());
+    // Some monsters are caught napping
+    let asleep = {
+        let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+        rng.roll_dice(1, 3) == 1
+    };
+    let builder = ecs
+        .create_entity()
         .with(Position { x, y })
         .with(Renderable {
             glyph,
-            fg: RGB::named(rltk::WHITE),
+            fg,
             bg: RGB::named(rltk::BLACK),
             render_order: 1,
         })
@@ -149,8 +619,27 @@ fn monster<S: ToString>(
             defense: defense,
             power: power,
         })
+        .with(attributes)
+        .with(Skills::default())
+        .with(LootTable {
+            table: name.to_string(),
+        })
+        .marked::<SimpleMarker<SerializeMe>>();
+    if asleep {
+        builder.with(Asleep {})
+    } else {
+        builder
+    }
+}
+
+/// Abilities are bare entities carrying effect components, used by monsters the
+/// same way the player uses an item.
+fn ability<S: ToString>(ecs: &mut World, name: S) -> EntityBuilder<'_> {
+    ecs.create_entity()
+        .with(Name {
+            name: name.to_string(),
+        })
         .marked::<SimpleMarker<SerializeMe>>()
-        .build();
 }
 
 fn entity(ecs: &mut World, x: i32, y: i32, name: String, glyph: u16) -> EntityBuilder {
@@ -168,67 +657,595 @@ fn entity(ecs: &mut World, x: i32, y: i32, name: String, glyph: u16) -> EntityBu
 }
 
 fn item(ecs: &mut World, x: i32, y: i32, name: String, glyph: u16) -> EntityBuilder {
-    return entity(ecs, x, y, name, glyph).with(Item {});
+    let gold = ecs.fetch::<PriceList>().base_value(&name);
+    valued_item(ecs, x, y, name, glyph, gold)
+}
+
+fn valued_item<'a>(
+    ecs: &'a mut World,
+    x: i32,
+    y: i32,
+    name: String,
+    glyph: u16,
+    gold: i32,
+) -> EntityBuilder<'a> {
+    entity(ecs, x, y, name, glyph)
+        .with(Item {})
+        .with(Value { gold })
+}
+
+/// Magic items go by a made-up name until the player has identified them.
+fn magic_item<'a>(ecs: &'a mut World, x: i32, y: i32, name: &str, glyph: u16) -> EntityBuilder<'a> {
+    let (shown_name, fg) = {
+        let identification = ecs.fetch::<Identification>();
+        (identification.shown_name(name), identification.colour(name))
+    };
+    let gold = ecs.fetch::<PriceList>().base_value(name);
+    let builder = valued_item(ecs, x, y, shown_name.clone(), glyph, gold).with(Renderable {
+        glyph,
+        fg,
+        bg: RGB::named(rltk::BLACK),
+        render_order: 2,
+    });
+    if shown_name == name {
+        builder
+    } else {
+        builder.with(Unidentified {
+            real_name: name.to_string(),
+        })
+    }
 }
 
 fn ketchup(ecs: &mut World, x: i32, y: i32) {
     item(ecs, x, y, "Ketchup".to_string(), 5)
         .with(Consumable {})
+        .with(Stackable { quantity: 1 })
+        .with(Fragile {})
         .with(ProvidesHealing { heal_amount: 8 })
+        .with(Weight { pounds: 1.0 })
+        .build();
+}
+
+fn food<'a>(ecs: &'a mut World, x: i32, y: i32, name: &str, fg: RGB) -> EntityBuilder<'a> {
+    item(ecs, x, y, name.to_string(), 9)
+        .with(Renderable {
+            glyph: 9,
+            fg,
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Consumable {})
+        .with(Stackable { quantity: 1 })
+        .with(Weight { pounds: 0.5 })
+        .with(ProvidesFood {})
+}
+
+fn dog_biscuit(ecs: &mut World, x: i32, y: i32) {
+    food(ecs, x, y, "Dog Biscuit", RGB::named(rltk::BURLYWOOD)).build();
+}
+
+fn bacon(ecs: &mut World, x: i32, y: i32) {
+    // Bacon is good for what ails you, too
+    food(ecs, x, y, "Bacon", RGB::named(rltk::INDIAN_RED))
+        .with(ProvidesHealing { heal_amount: 4 })
+        .build();
+}
+
+fn hot_sauce(ecs: &mut World, x: i32, y: i32) {
+    magic_item(ecs, x, y, "Hot Sauce", 5)
+        .with(Consumable {})
+        .with(Stackable { quantity: 1 })
+        .with(Fragile {})
+        .with(InflictsStatus {
+            effect: StatusEffect::Hasted,
+            turns: 10,
+        })
+        .with(Weight { pounds: 1.0 })
         .build();
 }
 
-fn scroll(ecs: &mut World, x: i32, y: i32, name: String, glyph: u16) -> EntityBuilder {
-    return item(ecs, x, y, name, glyph).with(Consumable {});
+fn chicken_soup(ecs: &mut World, x: i32, y: i32) {
+    magic_item(ecs, x, y, "Chicken Soup", 5)
+        .with(Consumable {})
+        .with(Stackable { quantity: 1 })
+        .with(Fragile {})
+        .with(InflictsStatus {
+            effect: StatusEffect::Regenerating,
+            turns: 15,
+        })
+        .with(Weight { pounds: 1.5 })
+        .build();
+}
+
+fn gold(ecs: &mut World, x: i32, y: i32, map_depth: i32) {
+    let amount = ecs
+        .write_resource::<RandomNumberGenerator>()
+        .roll_dice(2, 6)
+        + map_depth * 3;
+    item(ecs, x, y, format!("{} Gold", amount), 9)
+        .with(Renderable {
+            glyph: 9,
+            fg: RGB::named(rltk::GOLD),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Gold { amount })
+        .build();
+}
+
+/// Sets up a hot-dog stand in a free corner of the room, stocked with a few
+/// things to sell.
+pub fn vendor(ecs: &mut World, room: &Rect, map_depth: i32) {
+    let spot = {
+        let positions = ecs.read_storage::<Position>();
+        let mut free = (room.y1 + 1..=room.y2)
+            .flat_map(|y| (room.x1 + 1..=room.x2).map(move |x| (x, y)))
+            .filter(|(x, y)| !positions.join().any(|pos| pos.x == *x && pos.y == *y));
+        free.next()
+    };
+    let (x, y) = match spot {
+        Some(spot) => spot,
+        None => return,
+    };
+
+    let stand = entity(ecs, x, y, "Hot-Dog Stand".to_string(), 11)
+        .with(Renderable {
+            glyph: 11,
+            fg: RGB::named(rltk::HOT_PINK),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 1,
+        })
+        .with(BlocksTile {})
+        .with(Vendor {})
+        .with(Purse {
+            gold: 50 + map_depth * 20,
+        })
+        .build();
+
+    let stock_table = vendor_table(map_depth);
+    let stock_count = ecs
+        .write_resource::<RandomNumberGenerator>()
+        .roll_dice(1, 3)
+        + 3;
+    for _i in 0..stock_count {
+        let name = stock_table.roll(&mut ecs.write_resource::<RandomNumberGenerator>());
+        spawn_named(ecs, x, y, &name, map_depth);
+    }
+
+    // Everything just spawned on the stand goes behind the counter, with
+    // stackable stock of the same name merged into one stack
+    let entities = ecs.entities();
+    let items = ecs.read_storage::<Item>();
+    let names = ecs.read_storage::<Name>();
+    let mut stacks = ecs.write_storage::<Stackable>();
+    let mut positions = ecs.write_storage::<Position>();
+    let mut backpack = ecs.write_storage::<InBackpack>();
+    let stock: Vec<Entity> = (&entities, &items, &positions)
+        .join()
+        .filter(|(_e, _item, pos)| pos.x == x && pos.y == y)
+        .map(|(e, _item, _pos)| e)
+        .collect();
+    let mut stocked: Vec<(String, Entity)> = Vec::new();
+    for item in stock {
+        positions.remove(item);
+        let name = names.get(item).unwrap().name.clone();
+        if let Some(quantity) = stacks.get(item).map(|stack| stack.quantity) {
+            let existing = stocked
+                .iter()
+                .find(|(stocked_name, _)| *stocked_name == name)
+                .map(|(_, stack_entity)| *stack_entity);
+            if let Some(stack_entity) = existing {
+                if let Some(stack) = stacks.get_mut(stack_entity) {
+                    stack.quantity += quantity;
+                }
+                entities.delete(item).expect("Delete failed");
+                continue;
+            }
+            stocked.push((name, item));
+        }
+        backpack
+            .insert(item, InBackpack { owner: stand })
+            .expect("Unable to stock vendor");
+    }
+}
+
+fn stove(ecs: &mut World, x: i32, y: i32) {
+    entity(ecs, x, y, "Stove".to_string(), 11)
+        .with(Renderable {
+            glyph: 11,
+            fg: RGB::named(rltk::DARK_ORANGE),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 3,
+        })
+        .with(Stove {})
+        .build();
+}
+
+/// Spawns one of the cookbook's raw ingredients, such as a monster drop.
+pub fn ingredient(ecs: &mut World, x: i32, y: i32, name: &str) {
+    let data = ecs.fetch::<Cookbook>().ingredient(name).cloned();
+    if let Some(data) = data {
+        let (r, g, b) = data.colour;
+        item(ecs, x, y, data.name, data.glyph)
+            .with(Renderable {
+                glyph: data.glyph,
+                fg: RGB::from_u8(r, g, b),
+                bg: RGB::named(rltk::BLACK),
+                render_order: 2,
+            })
+            .with(Ingredient {})
+            .with(Stackable { quantity: 1 })
+            .with(Weight {
+                pounds: data.weight,
+            })
+            .build();
+    }
+}
+
+/// Creates the dish a recipe makes, straight into the cook's backpack.
+pub fn dish(ecs: &mut World, recipe: &Recipe, cook: Entity) -> Entity {
+    let (r, g, b) = recipe.colour;
+    let gold = ecs.fetch::<PriceList>().base_value(&recipe.name);
+    let mut builder = ecs
+        .create_entity()
+        .with(Renderable {
+            glyph: recipe.glyph,
+            fg: RGB::from_u8(r, g, b),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: recipe.name.clone(),
+        })
+        .with(Item {})
+        .with(Value { gold })
+        .with(Consumable {})
+        .with(Stackable { quantity: 1 })
+        .with(Weight {
+            pounds: recipe.weight,
+        })
+        .with(InBackpack { owner: cook })
+        .marked::<SimpleMarker<SerializeMe>>();
+    if recipe.food {
+        builder = builder.with(ProvidesFood {});
+    }
+    if recipe.fragile {
+        builder = builder.with(Fragile {});
+    }
+    if let Some(heal_amount) = recipe.heal {
+        builder = builder.with(ProvidesHealing { heal_amount });
+    }
+    if let Some(status) = recipe.status {
+        builder = builder.with(InflictsStatus {
+            effect: status.effect,
+            turns: status.turns,
+        });
+    }
+    if let Some(damage) = recipe.damage {
+        builder = builder.with(InflictsDamage {
+            damage: damage.amount,
+            damage_type: damage.damage_type,
+        });
+    }
+    if let Some(range) = recipe.range {
+        builder = builder.with(Ranged { range });
+    }
+    if let Some(radius) = recipe.radius {
+        builder = builder.with(AreaOfEffect { radius });
+    }
+    builder.build()
+}
+
+/// Every scroll looks alike apart from its per-run colour.
+fn scroll<'a>(ecs: &'a mut World, x: i32, y: i32, name: &str) -> EntityBuilder<'a> {
+    magic_item(ecs, x, y, name, 7)
+        .with(Consumable {})
+        .with(Weight { pounds: 0.5 })
+        .with(Stackable { quantity: 1 })
 }
 
 fn meat_beam_scroll(ecs: &mut World, x: i32, y: i32) {
-    scroll(ecs, x, y, "Meat Beam Scroll".to_string(), 7)
+    scroll(ecs, x, y, "Meat Beam Scroll")
         .with(Ranged { range: 6 })
-        .with(InflictsDamage { damage: 8 })
+        .with(InflictsDamage {
+            damage: 8,
+            damage_type: DamageType::Fire,
+        })
         .build();
 }
 
 fn fireball_scroll(ecs: &mut World, x: i32, y: i32) {
-    scroll(ecs, x, y, "Fireball Scroll".to_string(), 6)
+    scroll(ecs, x, y, "Fireball Scroll")
         .with(Ranged { range: 6 })
-        .with(InflictsDamage { damage: 20 })
+        .with(InflictsDamage {
+            damage: 20,
+            damage_type: DamageType::Fire,
+        })
+        .with(InflictsStatus {
+            effect: StatusEffect::Burning,
+            turns: 3,
+        })
         .with(AreaOfEffect { radius: 3 })
         .build();
 }
 
 fn food_coma_scroll(ecs: &mut World, x: i32, y: i32) {
-    scroll(ecs, x, y, "Food Coma Scroll".to_string(), 8)
+    scroll(ecs, x, y, "Food Coma Scroll")
         .with(Ranged { range: 6 })
-        .with(Confusion { turns: 4 })
+        .with(InflictsStatus {
+            effect: StatusEffect::Confused,
+            turns: 4,
+        })
         .build();
 }
 
-fn melee_weapon(
-    ecs: &mut World,
+fn identify_scroll(ecs: &mut World, x: i32, y: i32) {
+    scroll(ecs, x, y, "Identify Scroll")
+        .with(IdentifiesItem {})
+        .build();
+}
+
+fn remove_curse_scroll(ecs: &mut World, x: i32, y: i32) {
+    scroll(ecs, x, y, "Remove Curse Scroll")
+        .with(RemovesCurse {})
+        .build();
+}
+
+fn wand<'a>(ecs: &'a mut World, x: i32, y: i32, name: &str) -> EntityBuilder<'a> {
+    let charges = ecs
+        .write_resource::<RandomNumberGenerator>()
+        .roll_dice(1, 4)
+        + 2;
+    magic_item(ecs, x, y, name, 1)
+        .with(Charges {
+            remaining: charges,
+            max: charges,
+            crumbles: false,
+        })
+        .with(Weight { pounds: 0.5 })
+}
+
+fn meat_beam_wand(ecs: &mut World, x: i32, y: i32) {
+    wand(ecs, x, y, "Wand of Meat Beams")
+        .with(Ranged { range: 6 })
+        .with(InflictsDamage {
+            damage: 8,
+            damage_type: DamageType::Fire,
+        })
+        .build();
+}
+
+fn food_coma_wand(ecs: &mut World, x: i32, y: i32) {
+    wand(ecs, x, y, "Wand of Food Coma")
+        .with(Ranged { range: 6 })
+        .with(InflictsStatus {
+            effect: StatusEffect::Confused,
+            turns: 4,
+        })
+        .build();
+}
+
+// A cheap gadget: a few good cracks of pepper before it falls apart.
+fn pepper_grinder(ecs: &mut World, x: i32, y: i32) {
+    item(ecs, x, y, "Pepper Grinder".to_string(), 5)
+        .with(Renderable {
+            glyph: 5,
+            fg: RGB::named(rltk::GREY),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Ranged { range: 4 })
+        .with(AreaOfEffect { radius: 1 })
+        .with(InflictsDamage {
+            damage: 3,
+            damage_type: DamageType::Spice,
+        })
+        .with(Charges {
+            remaining: 3,
+            max: 3,
+            crumbles: true,
+        })
+        .with(Weight { pounds: 1.0 })
+        .build();
+}
+
+#[allow(clippy::too_many_arguments)]
+fn melee_weapon<'a>(
+    ecs: &'a mut World,
     x: i32,
     y: i32,
     name: String,
     glyph: u16,
+    damage: &str,
+    damage_type: DamageType,
     power: i32,
-) -> EntityBuilder {
+) -> EntityBuilder<'a> {
     item(ecs, x, y, name, glyph)
         .with(Equippable {
             slot: EquipmentSlot::Melee,
         })
+        .with(MeleeWeapon {
+            damage: dice(damage),
+            damage_type,
+        })
         .with(MeleePowerBonus { power: power })
 }
 
 fn spatula(ecs: &mut World, x: i32, y: i32) {
-    melee_weapon(ecs, x, y, "Spatula".to_string(), 1, 1).build();
+    melee_weapon(
+        ecs,
+        x,
+        y,
+        "Spatula".to_string(),
+        1,
+        "1d4",
+        DamageType::Blunt,
+        1,
+    )
+    .with(Weight { pounds: 2.0 })
+    .build();
 }
 
 fn fork(ecs: &mut World, x: i32, y: i32) {
-    melee_weapon(ecs, x, y, "Fork".to_string(), 2, 2).build();
+    melee_weapon(
+        ecs,
+        x,
+        y,
+        "Fork".to_string(),
+        2,
+        "1d6",
+        DamageType::Slashing,
+        2,
+    )
+    .with(ThrowDamage {
+        damage: dice("1d4"),
+        damage_type: DamageType::Slashing,
+    })
+    .with(Weight { pounds: 1.0 })
+    .build();
 }
 
 fn bread_knife(ecs: &mut World, x: i32, y: i32) {
-    melee_weapon(ecs, x, y, "Bread Knife".to_string(), 0, 4).build();
+    melee_weapon(
+        ecs,
+        x,
+        y,
+        "Bread Knife".to_string(),
+        0,
+        "1d8",
+        DamageType::Slashing,
+        4,
+    )
+    .with(ThrowDamage {
+        damage: dice("1d6"),
+        damage_type: DamageType::Slashing,
+    })
+    .with(Weight { pounds: 2.0 })
+    .build();
+}
+
+fn rolling_pin(ecs: &mut World, x: i32, y: i32) {
+    melee_weapon(
+        ecs,
+        x,
+        y,
+        "Rolling Pin".to_string(),
+        1,
+        "1d10",
+        DamageType::Blunt,
+        3,
+    )
+    .with(Renderable {
+        glyph: 1,
+        fg: RGB::named(rltk::BURLYWOOD),
+        bg: RGB::named(rltk::BLACK),
+        render_order: 2,
+    })
+    .with(TwoHanded {})
+    .with(Weight { pounds: 4.0 })
+    .build();
+}
+
+fn ranged_weapon(
+    ecs: &mut World,
+    x: i32,
+    y: i32,
+    name: String,
+    fg: RGB,
+    weapon: RangedWeapon,
+) -> EntityBuilder<'_> {
+    item(ecs, x, y, name, 1)
+        .with(Renderable {
+            glyph: 1,
+            fg,
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Equippable {
+            slot: EquipmentSlot::Ranged,
+        })
+        .with(weapon)
+}
+
+fn peashooter(ecs: &mut World, x: i32, y: i32) {
+    let weapon = RangedWeapon {
+        range: 6,
+        damage: dice("1d4"),
+        damage_type: DamageType::Blunt,
+        ammo: "Dried Peas".to_string(),
+    };
+    ranged_weapon(
+        ecs,
+        x,
+        y,
+        "Peashooter".to_string(),
+        RGB::named(rltk::LIME_GREEN),
+        weapon,
+    )
+    .with(Weight { pounds: 2.0 })
+    .build();
+}
+
+fn ketchup_squirter(ecs: &mut World, x: i32, y: i32) {
+    let weapon = RangedWeapon {
+        range: 4,
+        damage: dice("1d8"),
+        damage_type: DamageType::Spice,
+        ammo: "Ketchup Packets".to_string(),
+    };
+    ranged_weapon(
+        ecs,
+        x,
+        y,
+        "Ketchup Squirter".to_string(),
+        RGB::named(rltk::ORANGE_RED),
+        weapon,
+    )
+    .with(Weight { pounds: 3.0 })
+    .build();
+}
+
+#[allow(clippy::too_many_arguments)]
+fn ammunition(
+    ecs: &mut World,
+    x: i32,
+    y: i32,
+    name: &str,
+    glyph: u16,
+    fg: RGB,
+    count: i32,
+    pounds: f32,
+) {
+    item(ecs, x, y, name.to_string(), glyph)
+        .with(Renderable {
+            glyph,
+            fg,
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Ammunition {
+            kind: name.to_string(),
+        })
+        .with(Stackable { quantity: count })
+        .with(Weight { pounds })
+        .build();
+}
+
+fn dried_peas(ecs: &mut World, x: i32, y: i32) {
+    ammunition(ecs, x, y, "Dried Peas", 9, RGB::named(rltk::GREEN), 12, 0.1);
+}
+
+fn ketchup_packets(ecs: &mut World, x: i32, y: i32) {
+    ammunition(
+        ecs,
+        x,
+        y,
+        "Ketchup Packets",
+        5,
+        RGB::named(rltk::DARK_RED),
+        6,
+        0.2,
+    );
 }
 
 fn base_shield(
@@ -247,9 +1264,111 @@ fn base_shield(
 }
 
 fn shield(ecs: &mut World, x: i32, y: i32) {
-    base_shield(ecs, x, y, "Bread Shield".to_string(), 10, 1).build();
+    base_shield(ecs, x, y, "Bread Shield".to_string(), 10, 1)
+        .with(Weight { pounds: 6.0 })
+        .build();
 }
 
 fn tower_shield(ecs: &mut World, x: i32, y: i32) {
-    base_shield(ecs, x, y, "Dwarven Bread Shield".to_string(), 11, 3).build();
+    base_shield(ecs, x, y, "Dwarven Bread Shield".to_string(), 11, 3)
+        .with(Weight { pounds: 15.0 })
+        .build();
+}
+
+/// Worn gear for the slots other than weapons and shields.
+#[allow(clippy::too_many_arguments)]
+fn armour<'a>(
+    ecs: &'a mut World,
+    x: i32,
+    y: i32,
+    name: &str,
+    glyph: u16,
+    fg: RGB,
+    slot: EquipmentSlot,
+    pounds: f32,
+) -> EntityBuilder<'a> {
+    item(ecs, x, y, name.to_string(), glyph)
+        .with(Renderable {
+            glyph,
+            fg,
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Equippable { slot })
+        .with(Weight { pounds })
+}
+
+fn colander_helmet(ecs: &mut World, x: i32, y: i32) {
+    armour(
+        ecs,
+        x,
+        y,
+        "Colander Helmet",
+        10,
+        RGB::named(rltk::SILVER),
+        EquipmentSlot::Head,
+        2.0,
+    )
+    .with(DefenseBonus { defense: 1 })
+    .build();
+}
+
+fn chefs_apron(ecs: &mut World, x: i32, y: i32) {
+    armour(
+        ecs,
+        x,
+        y,
+        "Chef's Apron",
+        10,
+        RGB::named(rltk::WHITE_SMOKE),
+        EquipmentSlot::Body,
+        3.0,
+    )
+    .with(DefenseBonus { defense: 1 })
+    .build();
+}
+
+fn oven_mitts(ecs: &mut World, x: i32, y: i32) {
+    armour(
+        ecs,
+        x,
+        y,
+        "Oven Mitts",
+        10,
+        RGB::named(rltk::INDIAN_RED),
+        EquipmentSlot::Hands,
+        1.0,
+    )
+    .with(DefenseBonus { defense: 1 })
+    .build();
+}
+
+fn fluffy_slippers(ecs: &mut World, x: i32, y: i32) {
+    armour(
+        ecs,
+        x,
+        y,
+        "Fluffy Slippers",
+        10,
+        RGB::named(rltk::PINK),
+        EquipmentSlot::Feet,
+        1.0,
+    )
+    .with(DefenseBonus { defense: 1 })
+    .build();
+}
+
+fn onion_ring(ecs: &mut World, x: i32, y: i32) {
+    armour(
+        ecs,
+        x,
+        y,
+        "Onion Ring",
+        9,
+        RGB::named(rltk::GOLD),
+        EquipmentSlot::Ring,
+        0.1,
+    )
+    .with(MeleePowerBonus { power: 1 })
+    .build();
 }
@@ -0,0 +1,89 @@
+use super::{
+    CombatStats, DamageType, GameLog, Player, RunState, StatusEffect, StatusEffects, SufferDamage,
+};
+use specs::prelude::*;
+
+const POISON_DAMAGE: i32 = 1;
+const BURN_DAMAGE: i32 = 2;
+
+pub struct StatusEffectSystem {}
+
+impl<'a> System<'a> for StatusEffectSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        ReadExpect<'a, RunState>,
+        WriteExpect<'a, GameLog>,
+        Entities<'a>,
+        WriteStorage<'a, StatusEffects>,
+        WriteStorage<'a, CombatStats>,
+        WriteStorage<'a, SufferDamage>,
+        ReadStorage<'a, Player>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (runstate, mut log, entities, mut statuses, mut combat_stats, mut damage, players) =
+            data;
+
+        // Everyone's effects tick on their own turn: the player's on the player turn,
+        // everybody else's on the monster turn.
+        let players_turn = match *runstate {
+            RunState::PlayerTurn => true,
+            RunState::MonsterTurn => false,
+            _ => return,
+        };
+
+        let mut expired: Vec<Entity> = Vec::new();
+        for (entity, status) in (&entities, &mut statuses).join() {
+            let is_player = players.get(entity).is_some();
+            if is_player != players_turn {
+                continue;
+            }
+
+            for active in status.active.iter_mut() {
+                match active.effect {
+                    StatusEffect::Poisoned => SufferDamage::new_damage(
+                        &mut damage,
+                        entity,
+                        POISON_DAMAGE,
+                        DamageType::Poison,
+                        active.source.unwrap_or(entity),
+                        active.item,
+                    ),
+                    StatusEffect::Burning => SufferDamage::new_damage(
+                        &mut damage,
+                        entity,
+                        BURN_DAMAGE,
+                        DamageType::Fire,
+                        active.source.unwrap_or(entity),
+                        active.item,
+                    ),
+                    StatusEffect::Regenerating => {
+                        if let Some(stats) = combat_stats.get_mut(entity) {
+                            stats.hp = i32::min(stats.max_hp, stats.hp + 1);
+                        }
+                    }
+                    StatusEffect::WellFed if active.turns % 2 == 0 => {
+                        if let Some(stats) = combat_stats.get_mut(entity) {
+                            stats.hp = i32::min(stats.max_hp, stats.hp + 1);
+                        }
+                    }
+                    _ => {}
+                }
+
+                active.turns -= 1;
+                if active.turns < 1 && is_player {
+                    log.entries
+                        .push(format!("You are no longer {}.", active.effect.name()));
+                }
+            }
+            status.active.retain(|s| s.turns > 0);
+            if status.active.is_empty() {
+                expired.push(entity);
+            }
+        }
+
+        for entity in expired {
+            statuses.remove(entity);
+        }
+    }
+}